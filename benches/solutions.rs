@@ -0,0 +1,108 @@
+//! One benchmark per registered day and part, run against the real inputs.
+//!
+//! Parts with more than one implementation get a benchmark for each. Days without an
+//! input in `inputs/{year}/` are skipped. Pick days with divan's filters,
+//! e.g. `cargo bench -- 2025-day08` or `cargo bench -- --skip day09`.
+
+use aoc2025::{
+    DEFAULT_IMPLEMENTATION, DynSolution, RunContext, SolutionInfo, get_input, list_solutions,
+};
+use divan::Bencher;
+
+fn main() {
+    divan::main();
+}
+
+#[derive(Clone)]
+struct Day {
+    info: &'static SolutionInfo,
+    input: String,
+}
+
+impl std::fmt::Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-day{:02}", self.info.year, self.info.day)
+    }
+}
+
+fn days() -> Vec<Day> {
+    list_solutions()
+        .into_iter()
+        .filter_map(|info| {
+            let input = get_input(info.year, info.day).ok()?;
+            Some(Day {
+                info,
+                input: info.normalize.apply(&input),
+            })
+        })
+        .collect()
+}
+
+/// Reading the input from disk and normalizing it.
+#[divan::bench(args = days())]
+fn input(day: &Day) -> String {
+    day.info
+        .normalize
+        .apply(&get_input(day.info.year, day.info.day).unwrap())
+}
+
+/// Parsing the input, which happens once and is shared by both parts.
+#[divan::bench(args = days())]
+fn parse(bencher: Bencher, day: &Day) {
+    bencher
+        .with_inputs(|| (day.info.new)())
+        .bench_local_values(|mut solution| solution.parse(&day.input));
+}
+
+fn parsed(day: &Day) -> Box<dyn DynSolution> {
+    let mut solution = (day.info.new)();
+    solution.parse(&day.input).unwrap();
+    solution
+}
+
+/// One implementation of a part of a day, named like `2025-day07` for the default
+/// implementation and `2025-day07/sweep` for the others.
+struct Implementation {
+    day: Day,
+    name: &'static str,
+}
+
+impl std::fmt::Display for Implementation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name {
+            DEFAULT_IMPLEMENTATION => write!(f, "{}", self.day),
+            name => write!(f, "{}/{}", self.day, name),
+        }
+    }
+}
+
+fn implementations(part: u8) -> Vec<Implementation> {
+    days()
+        .into_iter()
+        .flat_map(|day| {
+            (day.info.new)()
+                .implementations(part)
+                .into_iter()
+                .map(move |name| Implementation {
+                    day: day.clone(),
+                    name,
+                })
+        })
+        .collect()
+}
+
+#[divan::bench(args = implementations(1))]
+fn part1(bencher: Bencher, implementation: &Implementation) {
+    let context = RunContext::real().with_implementation(implementation.name);
+    bencher
+        .with_inputs(|| parsed(&implementation.day))
+        .bench_local_values(|mut solution| solution.part1(&context));
+}
+
+#[divan::bench(args = implementations(2))]
+fn part2(bencher: Bencher, implementation: &Implementation) {
+    let context = RunContext::real().with_implementation(implementation.name);
+    bencher
+        .with_inputs(|| parsed(&implementation.day))
+        .bench_local_values(|mut solution| solution.part2(&context));
+}
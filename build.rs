@@ -0,0 +1,62 @@
+//! Writes `$OUT_DIR/embedded_inputs.rs`, the inputs baked into the binary by the
+//! `embed-inputs` feature: every `inputs/{year}/*.txt` there is at build time, as a
+//! list of `(year, file name, contents)`. Without the feature the list is empty.
+
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Every `{year}/*.txt` under `dir`, sorted, or nothing if there's no such directory.
+fn inputs(dir: &Path) -> Vec<(u16, String, PathBuf)> {
+    let mut inputs = vec![];
+
+    for year in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let Some(year_number) = year.file_name().to_str().and_then(|name| name.parse().ok()) else {
+            continue;
+        };
+
+        for file in fs::read_dir(year.path()).into_iter().flatten().flatten() {
+            let path = file.path();
+            if path.extension().is_some_and(|extension| extension == "txt") {
+                let name = file.file_name().to_string_lossy().into_owned();
+                inputs.push((year_number, name, path));
+            }
+        }
+    }
+
+    inputs.sort();
+    inputs
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+
+    let inputs = if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        let dir = manifest_dir.join("inputs");
+        // Also reruns when files are added to or removed from the directory
+        println!("cargo::rerun-if-changed={}", dir.display());
+        inputs(&dir)
+    } else {
+        println!("cargo::rerun-if-changed=build.rs");
+        vec![]
+    };
+
+    let mut code = "&[\n".to_string();
+    for (year, name, path) in inputs {
+        writeln!(
+            code,
+            "    ({}, {:?}, include_str!({:?})),",
+            year,
+            name,
+            path.display().to_string()
+        )
+        .unwrap();
+    }
+    code.push_str("]\n");
+
+    fs::write(&out, code).unwrap();
+}
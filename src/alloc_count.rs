@@ -0,0 +1,200 @@
+//! Allocation counting for the `count-allocations` feature, which installs
+//! [`CountingAllocator`] as the global allocator.
+//!
+//! Counts are kept per thread, so a part's numbers stay accurate when days run in
+//! parallel, but allocations a solution makes on other threads aren't counted.
+
+#[cfg(feature = "count-allocations")]
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Whether allocations are being counted in this build.
+pub const ENABLED: bool = cfg!(feature = "count-allocations");
+
+/// Allocations made while running something.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    /// Total bytes requested, including memory that was freed again
+    pub bytes: u64,
+    /// The most memory held at once, beyond what was held before
+    pub peak: u64,
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.50 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.2} {}", value, UNITS[unit])
+}
+
+impl std::fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+#[cfg(feature = "count-allocations")]
+#[derive(Debug, Clone, Copy)]
+struct Counts {
+    allocations: u64,
+    bytes: u64,
+    /// Bytes held by this thread, which goes negative when it frees memory allocated
+    /// on another thread
+    current: i64,
+    peak: i64,
+}
+
+#[cfg(feature = "count-allocations")]
+thread_local! {
+    // `const` with no destructor, so the allocator can use it at any point in a thread's life
+    static COUNTS: Cell<Counts> = const {
+        Cell::new(Counts {
+            allocations: 0,
+            bytes: 0,
+            current: 0,
+            peak: 0,
+        })
+    };
+}
+
+#[cfg(feature = "count-allocations")]
+fn update(f: impl FnOnce(&mut Counts)) {
+    // Fails only while the thread is being torn down, when nothing is being measured
+    let _ = COUNTS.try_with(|counts| {
+        let mut value = counts.get();
+        f(&mut value);
+        counts.set(value);
+    });
+}
+
+/// The system allocator, counting what each thread allocates.
+#[cfg(feature = "count-allocations")]
+pub struct CountingAllocator;
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[cfg(feature = "count-allocations")]
+fn allocated(size: usize) {
+    update(|counts| {
+        counts.allocations += 1;
+        counts.bytes += size as u64;
+        counts.current += size as i64;
+        counts.peak = counts.peak.max(counts.current);
+    });
+}
+
+#[cfg(feature = "count-allocations")]
+fn freed(size: usize) {
+    update(|counts| counts.current -= size as i64);
+}
+
+// SAFETY: every call is forwarded to `System` unchanged; counting doesn't allocate
+#[cfg(feature = "count-allocations")]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Runs `f`, returning what it allocated on this thread, or `None` without the
+/// `count-allocations` feature.
+#[cfg(feature = "count-allocations")]
+pub fn track<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    let mut before = COUNTS.get();
+    // Measure the peak from here, not from whatever came before
+    update(|counts| counts.peak = counts.current);
+    before.peak = before.current;
+
+    let result = f();
+    let after = COUNTS.get();
+
+    let stats = AllocStats {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+        peak: (after.peak - before.peak).max(0) as u64,
+    };
+
+    (result, Some(stats))
+}
+
+/// Runs `f`, returning what it allocated on this thread, or `None` without the
+/// `count-allocations` feature.
+#[cfg(not(feature = "count-allocations"))]
+pub fn track<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    (f(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(12), "12 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.00 MiB");
+    }
+
+    #[cfg(feature = "count-allocations")]
+    #[test]
+    fn test_track() {
+        let (_, stats) = track(|| {
+            let first = std::hint::black_box(vec![0u8; 1000]);
+            drop(first);
+            std::hint::black_box(vec![0u8; 600])
+        });
+        let stats = stats.unwrap();
+
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.bytes, 1600);
+        assert_eq!(stats.peak, 1000);
+    }
+}
@@ -0,0 +1,103 @@
+/// The answer to one part of a day.
+#[derive(Debug, Clone, Default)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Big(i128),
+    Text(String),
+    /// The part hasn't been implemented yet
+    #[default]
+    Unsolved,
+}
+
+impl Answer {
+    pub fn is_unsolved(&self) -> bool {
+        matches!(self, Answer::Unsolved)
+    }
+
+    /// The answer as an integer, if it is one
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Answer::Signed(n) => Some(*n as i128),
+            Answer::Unsigned(n) => Some(*n as i128),
+            Answer::Big(n) => Some(*n),
+            Answer::Text(_) | Answer::Unsolved => None,
+        }
+    }
+}
+
+/// Integers compare numerically regardless of variant, so `Signed(3) == Unsigned(3)`.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Unsolved, Answer::Unsolved) => true,
+            _ => match (self.as_i128(), other.as_i128()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Unsolved => write!(f, "not implemented"),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(n as $target)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(Signed, i64, i8, i16, i32, i64, isize);
+impl_from_int!(Unsigned, u64, u8, u16, u32, u64, usize);
+impl_from_int!(Big, i128, i128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_equality() {
+        assert_eq!(Answer::from(3i16), Answer::from(3usize));
+        assert_eq!(Answer::from(-3i64), Answer::from(-3i128));
+        assert_ne!(Answer::from(3), Answer::from("3"));
+        assert_ne!(Answer::from(""), Answer::Unsolved);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+        assert_eq!(Answer::Unsolved.to_string(), "not implemented");
+    }
+}
@@ -0,0 +1,143 @@
+//! Iteration budgets, for turning a loop that never ends into an error that says which
+//! loop it was.
+//!
+//! A solution ticks a named budget in a loop it doesn't trust, giving a default limit:
+//!
+//! ```ignore
+//! budget::tick("queue", 1_000_000)?;
+//! ```
+//!
+//! Counts are kept per thread and the runner resets them before every run of a parse or
+//! part, so budgets don't carry over between parts, days or tests. `--budget queue=500`
+//! overrides a budget's limit for the whole run.
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    sync::RwLock,
+};
+
+use anyhow::Context;
+
+/// Limits set with `--budget`, overriding the defaults given to [`tick`].
+static LIMITS: RwLock<BTreeMap<String, u64>> = RwLock::new(BTreeMap::new());
+
+thread_local! {
+    static USED: RefCell<HashMap<&'static str, u64>> = RefCell::new(HashMap::new());
+}
+
+/// A budget ran out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetExceeded {
+    pub name: &'static str,
+    pub limit: u64,
+}
+
+impl std::fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "iteration budget {:?} ran out after {} iterations (change it with --budget {}=N)",
+            self.name, self.limit, self.name
+        )
+    }
+}
+
+impl std::error::Error for BudgetExceeded {}
+
+/// Overrides a budget's limit, whatever default its call sites give.
+pub fn set_limit(name: &str, limit: u64) {
+    LIMITS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(name.to_string(), limit);
+}
+
+fn limit(name: &str, default: u64) -> u64 {
+    let limits = LIMITS.read().unwrap_or_else(|e| e.into_inner());
+    limits.get(name).copied().unwrap_or(default)
+}
+
+/// Counts one iteration against the budget `name`, failing once it has been ticked more
+/// than its limit times since the last reset.
+pub fn tick(name: &'static str, default_limit: u64) -> Result<(), BudgetExceeded> {
+    let used = USED.with_borrow_mut(|used| {
+        let count = used.entry(name).or_default();
+        *count += 1;
+        *count
+    });
+
+    let limit = limit(name, default_limit);
+    if used > limit {
+        return Err(BudgetExceeded { name, limit });
+    }
+    Ok(())
+}
+
+/// How many times `name` was ticked on this thread since the last reset.
+pub fn used(name: &str) -> u64 {
+    USED.with_borrow(|used| used.get(name).copied().unwrap_or(0))
+}
+
+/// Starts `name` over on this thread.
+pub fn reset(name: &str) {
+    USED.with_borrow_mut(|used| used.remove(name));
+}
+
+/// Starts every budget over on this thread.
+pub fn reset_all() {
+    USED.with_borrow_mut(HashMap::clear);
+}
+
+/// Parses a `--budget` argument like `queue=500`.
+pub fn parse_limit(s: &str) -> anyhow::Result<(String, u64)> {
+    let (name, limit) = s
+        .split_once('=')
+        .with_context(|| format!("expected NAME=LIMIT, got {:?}", s))?;
+    let limit = limit
+        .trim()
+        .parse()
+        .with_context(|| format!("invalid limit in {:?}", s))?;
+    Ok((name.trim().to_string(), limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick() {
+        for _ in 0..3 {
+            tick("test_tick", 3).unwrap();
+        }
+        assert_eq!(used("test_tick"), 3);
+        assert_eq!(
+            tick("test_tick", 3),
+            Err(BudgetExceeded {
+                name: "test_tick",
+                limit: 3
+            })
+        );
+
+        reset("test_tick");
+        assert_eq!(used("test_tick"), 0);
+        assert!(tick("test_tick", 3).is_ok());
+
+        set_limit("test_tick_override", 1);
+        assert!(tick("test_tick_override", 100).is_ok());
+        assert!(tick("test_tick_override", 100).is_err());
+
+        reset_all();
+        assert_eq!(used("test_tick_override"), 0);
+    }
+
+    #[test]
+    fn test_parse_limit() {
+        assert_eq!(
+            parse_limit("queue=500").unwrap(),
+            ("queue".to_string(), 500)
+        );
+        assert!(parse_limit("queue").is_err());
+        assert!(parse_limit("queue=lots").is_err());
+    }
+}
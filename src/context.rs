@@ -0,0 +1,149 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use anyhow::{Context, Result};
+
+/// What a part is being run on, for the few days whose puzzle changes between the
+/// examples and the real input.
+///
+/// Parameters are named values a day reads with [`RunContext::param`], such as how many
+/// steps to simulate. Examples set their own with [`crate::Example::param`], and
+/// `--param name=value` overrides them from the command line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunContext {
+    example: bool,
+    params: BTreeMap<String, String>,
+    implementation: Option<String>,
+}
+
+impl RunContext {
+    /// Running on a real puzzle input.
+    pub fn real() -> Self {
+        Self::default()
+    }
+
+    /// Running on an example from the puzzle text.
+    pub fn example() -> Self {
+        Self {
+            example: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_example(&self) -> bool {
+        self.example
+    }
+
+    /// Sets a parameter, replacing any earlier value.
+    pub fn with_param(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Sets several parameters, replacing any earlier values.
+    pub fn with_params<'a>(
+        mut self,
+        params: impl IntoIterator<Item = &'a (String, String)>,
+    ) -> Self {
+        for (name, value) in params {
+            self.params.insert(name.clone(), value.clone());
+        }
+        self
+    }
+
+    /// Runs the implementation called `name` rather than the default, see
+    /// [`crate::Solution::implementations`].
+    pub fn with_implementation(mut self, name: &str) -> Self {
+        self.implementation = Some(name.to_string());
+        self
+    }
+
+    pub fn implementation(&self) -> Option<&str> {
+        self.implementation.as_deref()
+    }
+
+    /// This context with the parameters and implementation that `overrides` sets
+    /// replacing its own, for applying command line options over an example's.
+    pub fn overridden_by(mut self, overrides: &RunContext) -> Self {
+        self.params.extend(overrides.params.clone());
+        if overrides.implementation.is_some() {
+            self.implementation.clone_from(&overrides.implementation);
+        }
+        self
+    }
+
+    /// The parameter `name` parsed as a `T`, or `default` if it isn't set.
+    pub fn param<T: FromStr>(&self, name: &str, default: T) -> Result<T>
+    where
+        T::Err: Display,
+    {
+        match self.params.get(name) {
+            Some(value) => value
+                .parse()
+                .map_err(|e| anyhow::anyhow!("{}", e))
+                .with_context(|| format!("invalid value {:?} for parameter {}", value, name)),
+            None => Ok(default),
+        }
+    }
+}
+
+/// Parses a `--param` argument like `connections=10`.
+pub fn parse_param(s: &str) -> Result<(String, String)> {
+    let (name, value) = s
+        .split_once('=')
+        .with_context(|| format!("expected NAME=VALUE, got {:?}", s))?;
+    Ok((name.trim().to_string(), value.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_param() {
+        let context = RunContext::example()
+            .with_param("steps", 10)
+            .with_params(&[("steps".to_string(), "20".to_string())]);
+
+        assert!(context.is_example());
+        assert_eq!(context.param("steps", 5).unwrap(), 20);
+        assert_eq!(context.param("missing", 5).unwrap(), 5);
+        assert!(
+            RunContext::real()
+                .with_param("steps", "lots")
+                .param("steps", 5)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_overridden_by() {
+        let overrides = RunContext::real()
+            .with_param("steps", 20)
+            .with_implementation("fast");
+        let context = RunContext::example()
+            .with_param("steps", 10)
+            .with_param("width", 7)
+            .overridden_by(&overrides);
+
+        assert!(context.is_example());
+        assert_eq!(context.param("steps", 0).unwrap(), 20);
+        assert_eq!(context.param("width", 0).unwrap(), 7);
+        assert_eq!(context.implementation(), Some("fast"));
+        assert_eq!(
+            RunContext::real()
+                .with_implementation("slow")
+                .overridden_by(&RunContext::default())
+                .implementation(),
+            Some("slow")
+        );
+    }
+
+    #[test]
+    fn test_parse_param() {
+        assert_eq!(
+            parse_param("connections=10").unwrap(),
+            ("connections".to_string(), "10".to_string())
+        );
+        assert!(parse_param("connections").is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use crate::Answer;
+
+/// An example from a day's puzzle text, and the answers it should produce.
+#[derive(Debug, Clone)]
+pub struct Example {
+    /// Which ```text block in the module docs holds the input, counting from 0
+    pub block: usize,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    /// Parameters the example needs, see [`crate::RunContext::param`]
+    pub params: Vec<(String, String)>,
+}
+
+impl Example {
+    pub fn new(block: usize) -> Self {
+        Self {
+            block,
+            part1: None,
+            part2: None,
+            params: vec![],
+        }
+    }
+
+    /// Sets a parameter for this example, e.g. fewer steps than the real input takes.
+    pub fn param(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn part1(mut self, answer: impl Into<Answer>) -> Self {
+        self.part1 = Some(answer.into());
+        self
+    }
+
+    pub fn part2(mut self, answer: impl Into<Answer>) -> Self {
+        self.part2 = Some(answer.into());
+        self
+    }
+
+    pub fn expected(&self, part: u8) -> Option<Answer> {
+        match part {
+            1 => self.part1.clone(),
+            _ => self.part2.clone(),
+        }
+    }
+}
+
+/// Pulls the contents of every ```text block out of the `//!` docs in `source`.
+pub fn doc_blocks(source: &str) -> Vec<String> {
+    let mut blocks = vec![];
+    let mut current: Option<Vec<&str>> = None;
+
+    for line in source.lines() {
+        let Some(doc) = line.strip_prefix("//!") else {
+            continue;
+        };
+        let doc = doc.strip_prefix(' ').unwrap_or(doc);
+
+        match &mut current {
+            None if doc.trim_end() == "```text" => current = Some(vec![]),
+            Some(lines) if doc.trim_end() == "```" => {
+                blocks.push(lines.join("\n"));
+                current = None;
+            }
+            Some(lines) => lines.push(doc),
+            None => {}
+        }
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doc_blocks() {
+        let source = r#"//! Some puzzle
+//!
+//! ```text
+//! 1 2
+//!  3
+//! ```
+//! More text
+//! ```rust
+//! let ignored = true;
+//! ```
+//! ```text
+//! abc
+//! ```
+
+use crate::Solution;
+"#;
+
+        assert_eq!(doc_blocks(source), vec!["1 2\n 3", "abc"]);
+    }
+}
@@ -0,0 +1,12 @@
+//! Random inputs for stress testing, from each day's [`crate::Solution::generate`].
+
+use rand::{SeedableRng, rngs::StdRng};
+
+/// The `size` used when none is given, small enough for many seeds in a debug build.
+pub const DEFAULT_SIZE: usize = 100;
+
+/// The random number generator days generate inputs with, so a seed always gives the
+/// same input.
+pub fn rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
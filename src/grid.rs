@@ -1,5 +1,6 @@
 use std::io::Write;
 
+use anyhow::{Context, bail};
 use image::{EncodableLayout, ImageBuffer, Pixel, PixelWithColorType};
 use itertools::Itertools;
 
@@ -358,6 +359,54 @@ impl<T: PartialEq> Grid<T> {
         }
     }
 
+    /// Like [`Grid::new`], but reports the line and column of any character `char_to_t`
+    /// rejects, or any line whose width doesn't match the first, instead of panicking.
+    pub fn try_new(
+        input: &str,
+        char_to_t: impl Fn(char) -> Option<T>,
+        wrapping: bool,
+    ) -> anyhow::Result<Self> {
+        let mut data = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for (row, line) in input.lines().enumerate() {
+            let mut line_width = 0;
+
+            for (col, c) in line.chars().enumerate() {
+                data.push(char_to_t(c).with_context(|| {
+                    format!(
+                        "line {}, column {}: unexpected character {:?}",
+                        row + 1,
+                        col + 1,
+                        c
+                    )
+                })?);
+                line_width += 1;
+            }
+
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => bail!(
+                    "line {}: expected width {}, found {}",
+                    row + 1,
+                    width,
+                    line_width
+                ),
+                Some(_) => {}
+            }
+
+            height += 1;
+        }
+
+        Ok(Self {
+            data,
+            width: width.unwrap_or(0),
+            height,
+            wrapping,
+        })
+    }
+
     pub fn transpose(&self) -> Grid<T>
     where
         T: Clone,
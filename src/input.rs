@@ -0,0 +1,283 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+
+use crate::input_name;
+
+/// Environment variable that overrides the `inputs` directory.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Inputs baked into the binary with the `embed-inputs` feature, as
+/// `(year, file name, contents)`, see `build.rs`. Empty without the feature.
+static EMBEDDED: &[(u16, &str, &str)] = include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+/// The copy of `inputs/{year}/{name}` baked into the binary, if there is one.
+pub fn embedded(year: u16, name: &str) -> Option<&'static str> {
+    EMBEDDED
+        .iter()
+        .find(|&&(embedded_year, embedded_name, _)| embedded_year == year && embedded_name == name)
+        .map(|&(_, _, contents)| contents)
+}
+
+/// The directory a year's inputs are read from, `inputs/{year}` unless the `inputs`
+/// part is overridden by `AOC_INPUT_DIR`.
+pub fn input_dir(year: u16) -> PathBuf {
+    std::env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("inputs"))
+        .join(year.to_string())
+}
+
+/// Whether inputs are read from `inputs`, rather than a directory set with
+/// `AOC_INPUT_DIR` that may hold someone else's inputs.
+fn is_default_input_dir() -> bool {
+    std::env::var_os(INPUT_DIR_VAR).is_none()
+}
+
+/// Where a day's input comes from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// `{input_dir}/{year}/{day}_input.txt`
+    #[default]
+    Real,
+    /// Read everything from stdin
+    Stdin,
+    /// A specific file
+    Path(PathBuf),
+    /// `{input_dir}/{year}/{day}_{name}.txt`, e.g. `example` for `1_example.txt`
+    Named(String),
+}
+
+impl std::str::FromStr for InputSource {
+    type Err = std::convert::Infallible;
+
+    /// `-` is stdin, anything that looks like a path (has a `/` or `.`) is a file,
+    /// and any other word names a file in the input directory.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(if s == "-" {
+            InputSource::Stdin
+        } else if s.contains(['/', '\\', '.']) {
+            InputSource::Path(PathBuf::from(s))
+        } else {
+            InputSource::Named(s.to_string())
+        })
+    }
+}
+
+impl InputSource {
+    /// Whether this source can be read for every day, rather than naming a single file.
+    pub fn is_per_day(&self) -> bool {
+        matches!(self, InputSource::Real | InputSource::Named(_))
+    }
+
+    /// The file this source reads, or `None` for stdin.
+    pub fn path(&self, year: u16, day: u8) -> Option<PathBuf> {
+        match self {
+            InputSource::Real => Some(input_dir(year).join(input_name(day))),
+            InputSource::Stdin => None,
+            InputSource::Path(path) => Some(path.clone()),
+            InputSource::Named(name) => Some(input_dir(year).join(format!("{}_{}.txt", day, name))),
+        }
+    }
+
+    /// Whether this is the day's real input from the default `inputs` directory, the one
+    /// the answers hard-coded in solutions are for.
+    pub fn is_own_input(&self) -> bool {
+        *self == InputSource::Real && is_default_input_dir()
+    }
+
+    /// The key this input's answers are stored under in the known answers file, within
+    /// its year and day: the file name for inputs in the default `inputs` directory, and
+    /// the full path for any other file, so a file that happens to share a name with
+    /// our real input, like a teammate's under `AOC_INPUT_DIR`, isn't mistaken for it.
+    pub fn name(&self, year: u16, day: u8) -> String {
+        match self.path(year, day) {
+            Some(path) if self.is_per_day() && is_default_input_dir() => path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string()),
+            Some(path) => std::fs::canonicalize(&path)
+                .unwrap_or(path)
+                .display()
+                .to_string(),
+            None => "stdin".to_string(),
+        }
+    }
+
+    /// The copy of this input built into the binary, for sources in the default `inputs`
+    /// directory.
+    fn embedded(&self, year: u16, day: u8) -> Option<&'static str> {
+        if !self.is_per_day() || !is_default_input_dir() {
+            return None;
+        }
+        embedded(year, &self.name(year, day))
+    }
+
+    /// Reads the input, falling back to the copy built in with the `embed-inputs`
+    /// feature when an input directory file can't be read, so the binary works from
+    /// anywhere.
+    pub fn read(&self, year: u16, day: u8) -> Result<String> {
+        match self.path(year, day) {
+            Some(path) => read_file(year, day, &path)
+                .or_else(|e| self.embedded(year, day).map(str::to_string).ok_or(e)),
+            None => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .context("Failed to read input from stdin")?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// How much of the ends of an input to trim.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trim {
+    /// Keep the input as it is
+    None,
+    /// Drop the newlines at the end, keeping any other whitespace
+    TrailingNewline,
+    /// Drop all whitespace at the start and end
+    Full,
+}
+
+/// How a day's input is cleaned up before parsing, set with [`crate::Solution::NORMALIZE`].
+///
+/// Applied to every input a day runs on, whether it comes from a file, stdin or the
+/// examples in the puzzle text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Normalize {
+    /// Turn `\r\n` line endings into `\n`
+    pub crlf: bool,
+    /// Drop spaces and tabs at the end of every line
+    pub trailing_spaces: bool,
+    pub trim: Trim,
+}
+
+impl Normalize {
+    /// The input exactly as read.
+    pub const NONE: Self = Self {
+        crlf: false,
+        trailing_spaces: false,
+        trim: Trim::None,
+    };
+
+    /// Unix line endings and no whitespace around the input, which suits most days.
+    pub const DEFAULT: Self = Self {
+        crlf: true,
+        trailing_spaces: false,
+        trim: Trim::Full,
+    };
+
+    pub fn apply(&self, input: &str) -> String {
+        let mut input = if self.crlf {
+            input.replace("\r\n", "\n")
+        } else {
+            input.to_string()
+        };
+
+        if self.trailing_spaces {
+            input = input
+                .split('\n')
+                .map(|line| line.trim_end_matches([' ', '\t']))
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+
+        match self.trim {
+            Trim::None => input,
+            Trim::TrailingNewline => input.trim_end_matches(['\r', '\n']).to_string(),
+            Trim::Full => input.trim().to_string(),
+        }
+    }
+}
+
+impl Default for Normalize {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+fn read_file(year: u16, day: u8, path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).with_context(|| {
+        format!(
+            "Failed to read input for day {} of {} from {}",
+            day,
+            year,
+            path.display()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_source() {
+        assert_eq!("-".parse(), Ok(InputSource::Stdin));
+        assert_eq!(
+            "edge.txt".parse(),
+            Ok(InputSource::Path(PathBuf::from("edge.txt")))
+        );
+        assert_eq!(
+            "example".parse(),
+            Ok(InputSource::Named("example".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(InputSource::Real.name(2025, 3), "3_input.txt");
+        assert_eq!(
+            InputSource::Named("example".to_string()).name(2025, 3),
+            "3_example.txt"
+        );
+        assert_eq!(
+            InputSource::Path(PathBuf::from("/does/not/exist/3_input.txt")).name(2025, 3),
+            "/does/not/exist/3_input.txt"
+        );
+        assert_eq!(InputSource::Stdin.name(2025, 3), "stdin");
+    }
+
+    #[test]
+    fn test_normalize() {
+        let input = "  1 2  \r\n3\t\r\n\r\n";
+
+        assert_eq!(Normalize::NONE.apply(input), input);
+        assert_eq!(Normalize::DEFAULT.apply(input), "1 2  \n3");
+        assert_eq!(
+            Normalize {
+                crlf: true,
+                trailing_spaces: true,
+                trim: Trim::TrailingNewline,
+            }
+            .apply(input),
+            "  1 2\n3"
+        );
+        assert_eq!(
+            Normalize {
+                crlf: false,
+                trailing_spaces: false,
+                trim: Trim::TrailingNewline,
+            }
+            .apply(input),
+            "  1 2  \r\n3\t"
+        );
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let error = InputSource::Path(PathBuf::from("does/not/exist.txt"))
+            .read(2025, 3)
+            .unwrap_err();
+        assert!(
+            format!("{:#}", error)
+                .starts_with("Failed to read input for day 3 of 2025 from does/not/exist.txt: ")
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::{collections::BTreeMap, path::Path};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::Answer;
+
+/// Where known answers are read from and recorded to, relative to the current directory.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RecordedParts {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<toml::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<toml::Value>,
+}
+
+impl RecordedParts {
+    fn part(&self, part: u8) -> &Option<toml::Value> {
+        match part {
+            1 => &self.part1,
+            _ => &self.part2,
+        }
+    }
+
+    fn part_mut(&mut self, part: u8) -> &mut Option<toml::Value> {
+        match part {
+            1 => &mut self.part1,
+            _ => &mut self.part2,
+        }
+    }
+}
+
+/// Confirmed answers, keyed by year, day and then the input file they were computed from:
+///
+/// ```toml
+/// [2025.day1."1_input.txt"]
+/// part1 = 992
+/// part2 = 6133
+/// ```
+///
+/// Integers that don't fit in a TOML integer are stored as a table holding their digits,
+/// like `part2 = { big = "18446744073709551615" }`, so they can't be confused with text.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct KnownAnswers {
+    years: BTreeMap<String, Days>,
+}
+
+/// A year's answers by day, then by input file name
+type Days = BTreeMap<String, BTreeMap<String, RecordedParts>>;
+
+fn day_key(day: u8) -> String {
+    format!("day{}", day)
+}
+
+/// The key of the table an out of range integer is stored in
+const BIG_KEY: &str = "big";
+
+fn big_value(n: impl ToString) -> toml::Value {
+    let mut table = toml::Table::new();
+    table.insert(BIG_KEY.to_string(), toml::Value::String(n.to_string()));
+    toml::Value::Table(table)
+}
+
+fn answer_to_value(answer: &Answer) -> Option<toml::Value> {
+    let value = match answer {
+        Answer::Signed(n) => toml::Value::Integer(*n),
+        Answer::Unsigned(n) => i64::try_from(*n)
+            .map(toml::Value::Integer)
+            .unwrap_or_else(|_| big_value(n)),
+        Answer::Big(n) => i64::try_from(*n)
+            .map(toml::Value::Integer)
+            .unwrap_or_else(|_| big_value(n)),
+        Answer::Text(s) => toml::Value::String(s.clone()),
+        Answer::Unsolved => return None,
+    };
+
+    Some(value)
+}
+
+fn value_to_answer(value: &toml::Value) -> Option<Answer> {
+    match value {
+        toml::Value::Integer(n) => Some(Answer::Signed(*n)),
+        toml::Value::String(s) => Some(Answer::Text(s.clone())),
+        toml::Value::Table(table) => match table.get(BIG_KEY)? {
+            toml::Value::String(s) => s.parse().ok().map(Answer::Big),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl KnownAnswers {
+    /// Reads known answers from `path`, treating a missing file as having none.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+
+        match std::fs::read_to_string(path) {
+            Ok(contents) => {
+                toml::from_str(&contents).with_context(|| format!("Failed to parse {:?}", path))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {:?}", path)),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let contents = toml::to_string(self)?;
+
+        std::fs::write(path, contents).with_context(|| format!("Failed to write {:?}", path))
+    }
+
+    pub fn get(&self, year: u16, day: u8, input_name: &str, part: u8) -> Option<Answer> {
+        self.years
+            .get(&year.to_string())?
+            .get(&day_key(day))?
+            .get(input_name)?
+            .part(part)
+            .as_ref()
+            .and_then(value_to_answer)
+    }
+
+    /// Stores `answer` as known, ignoring parts that aren't solved yet.
+    pub fn record(&mut self, year: u16, day: u8, input_name: &str, part: u8, answer: &Answer) {
+        let Some(value) = answer_to_value(answer) else {
+            return;
+        };
+
+        *self
+            .years
+            .entry(year.to_string())
+            .or_default()
+            .entry(day_key(day))
+            .or_default()
+            .entry(input_name.to_string())
+            .or_default()
+            .part_mut(part) = Some(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut known = KnownAnswers::default();
+        known.record(2025, 1, "1_input.txt", 1, &Answer::from(992));
+        known.record(2025, 1, "1_input.txt", 2, &Answer::Unsolved);
+        known.record(2025, 2, "2_input.txt", 2, &Answer::from(u64::MAX));
+        known.record(2024, 10, "example.txt", 1, &Answer::from("abc"));
+        known.record(2024, 10, "example.txt", 2, &Answer::from("0123"));
+
+        let contents = toml::to_string(&known).unwrap();
+        let known: KnownAnswers = toml::from_str(&contents).unwrap();
+
+        assert_eq!(
+            known.get(2025, 1, "1_input.txt", 1),
+            Some(Answer::from(992))
+        );
+        assert_eq!(known.get(2025, 1, "1_input.txt", 2), None);
+        assert_eq!(known.get(2025, 1, "other.txt", 1), None);
+        assert_eq!(
+            known.get(2025, 2, "2_input.txt", 2),
+            Some(Answer::from(u64::MAX))
+        );
+        assert_eq!(
+            known.get(2024, 10, "example.txt", 1),
+            Some(Answer::from("abc"))
+        );
+        assert_eq!(
+            known.get(2024, 10, "example.txt", 2),
+            Some(Answer::from("0123"))
+        );
+        assert_eq!(known.get(2025, 10, "example.txt", 1), None);
+    }
+}
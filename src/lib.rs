@@ -1,86 +1,485 @@
+#![feature(associated_type_defaults)]
+
+use anyhow::{Context, Result};
+use input::Normalize;
+
+pub use answer::Answer;
+pub use context::RunContext;
+pub use examples::Example;
+
+/// Turns a day's raw input into the value both of its parts work on.
+pub trait FromInput: Sized {
+    fn from_input(input: &str) -> Result<Self>;
+}
+
+impl FromInput for String {
+    fn from_input(input: &str) -> Result<Self> {
+        Ok(input.to_string())
+    }
+}
+
+/// The name `part1` and `part2` go by next to a day's other [`Implementation`]s.
+pub const DEFAULT_IMPLEMENTATION: &str = "default";
+
+/// Another way of solving one part, such as a naive version kept to check a faster one
+/// against. Picked with `--impl` and timed against the rest with `compare`.
+pub struct Implementation<S: Solution> {
+    pub name: &'static str,
+    pub part: u8,
+    pub run: fn(&mut S, &S::Parsed, &RunContext) -> Result<Answer>,
+}
+
+impl<S: Solution> Implementation<S> {
+    pub fn part1(
+        name: &'static str,
+        run: fn(&mut S, &S::Parsed, &RunContext) -> Result<Answer>,
+    ) -> Self {
+        Self { name, part: 1, run }
+    }
+
+    pub fn part2(
+        name: &'static str,
+        run: fn(&mut S, &S::Parsed, &RunContext) -> Result<Answer>,
+    ) -> Self {
+        Self { name, part: 2, run }
+    }
+}
+
 pub trait Solution {
+    /// What the input is parsed into once, before either part runs. Days that work on
+    /// the raw text keep the default.
+    type Parsed: FromInput = String;
+
+    /// How the input is cleaned up before [`Solution::parse`] sees it. Days whose input
+    /// lines up in columns should keep their leading and trailing spaces.
+    const NORMALIZE: Normalize = Normalize::DEFAULT;
+
     fn new() -> Self
     where
         Self: Sized;
 
-    fn part1(&mut self, _input: &str) -> String {
-        "".to_string()
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Self::Parsed::from_input(input)
+    }
+
+    fn part1(&mut self, _input: &Self::Parsed, _context: &RunContext) -> Result<Answer> {
+        Ok(Answer::Unsolved)
     }
-    fn part2(&mut self, _input: &str) -> String {
-        "".to_string()
+    fn part2(&mut self, _input: &Self::Parsed, _context: &RunContext) -> Result<Answer> {
+        Ok(Answer::Unsolved)
     }
 
-    fn known_solution_part1(&self) -> Option<String> {
+    fn known_solution_part1(&self) -> Option<Answer> {
         None
     }
 
-    fn known_solution_part2(&self) -> Option<String> {
+    fn known_solution_part2(&self) -> Option<Answer> {
+        None
+    }
+
+    /// Examples from the puzzle text in the module docs, see [`examples::doc_blocks`].
+    fn examples(&self) -> Vec<Example> {
+        vec![]
+    }
+
+    /// Implementations of the parts besides `part1` and `part2`, which are called
+    /// [`DEFAULT_IMPLEMENTATION`].
+    fn implementations(&self) -> Vec<Implementation<Self>>
+    where
+        Self: Sized,
+    {
+        vec![]
+    }
+
+    /// A random but valid input for stress testing, the same for the same `seed`, with
+    /// `size` setting roughly how many lines or items it has. `None` if the day has no
+    /// generator. See [`generate::rng`].
+    fn generate(&self, _seed: u64, _size: usize) -> Option<String> {
         None
     }
+
+    /// Parameters generated inputs of `size` are run with, like an example's
+    /// [`Example::param`], for the days whose real parameters only suit the real input.
+    fn generated_params(&self, _size: usize) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    /// Normalizes and parses `input` and runs one part on it as a real input, mostly for
+    /// tests.
+    fn solve(&mut self, part: u8, input: &str) -> Result<Answer>
+    where
+        Self: Sized,
+    {
+        self.solve_with(part, input, &RunContext::real())
+    }
+
+    /// Like [`Solution::solve`], with the parameters and mode in `context`.
+    fn solve_with(&mut self, part: u8, input: &str, context: &RunContext) -> Result<Answer>
+    where
+        Self: Sized,
+    {
+        let input = self.parse(&Self::NORMALIZE.apply(input))?;
+        match part {
+            1 => self.part1(&input, context),
+            _ => self.part2(&input, context),
+        }
+    }
 }
 
-pub mod day1;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-pub mod direction;
-pub mod grid;
+/// A [`Solution`] with its parsed input type hidden, so every day can be run the same way.
+///
+/// `parse` keeps the parsed input for the parts that follow, and the parts run the
+/// implementation the context names. Solutions are `Send` so a part can run on a worker
+/// thread under `--timeout`.
+pub trait DynSolution: Send {
+    fn parse(&mut self, input: &str) -> Result<()>;
+    fn part1(&mut self, context: &RunContext) -> Result<Answer>;
+    fn part2(&mut self, context: &RunContext) -> Result<Answer>;
+    fn known_solution_part1(&self) -> Option<Answer>;
+    fn known_solution_part2(&self) -> Option<Answer>;
+    fn examples(&self) -> Vec<Example>;
+    /// The names of a part's implementations, starting with [`DEFAULT_IMPLEMENTATION`].
+    fn implementations(&self, part: u8) -> Vec<&'static str>;
+    fn generate(&self, seed: u64, size: usize) -> Option<String>;
+    fn generated_params(&self, size: usize) -> Vec<(String, String)>;
+}
 
-pub fn get_solution(day: u8) -> Box<dyn Solution> {
-    match day {
-        1 => Box::new(day1::Day1::new()),
-        2 => Box::new(day2::Day2::new()),
-        3 => Box::new(day3::Day3::new()),
-        4 => Box::new(day4::Day4::new()),
-        5 => Box::new(day5::Day5::new()),
-        6 => Box::new(day6::Day6::new()),
-        7 => Box::new(day7::Day7::new()),
-        8 => Box::new(day8::Day8::new()),
-        9 => Box::new(day9::Day9::new()),
-        10 => Box::new(day10::Day10::new()),
-        11 => Box::new(day11::Day11::new()),
-        12 => Box::new(day12::Day12::new()),
-        _ => panic!("Invalid day"),
-    }
-}
-
-pub fn get_input(day: u8) -> String {
-    // File path is in inputs/{}_input.txt
-    std::fs::read_to_string(format!("inputs/{}_input.txt", day)).unwrap()
-}
-
-pub fn panic_after(count: usize) {
-    static mut COUNT: usize = 0;
-    unsafe {
-        COUNT += 1;
-        if COUNT >= count {
-            panic!("Panic after {} iterations", count);
+pub(crate) struct Erased<S: Solution> {
+    solution: S,
+    parsed: Option<S::Parsed>,
+}
+
+impl<S: Solution> Erased<S> {
+    pub(crate) fn new() -> Self {
+        Self {
+            solution: S::new(),
+            parsed: None,
         }
     }
 }
 
+const NOT_PARSED: &str = "the input must be parsed before running a part";
+
+impl<S: Solution + Send> Erased<S>
+where
+    S::Parsed: Send,
+{
+    fn run(&mut self, part: u8, context: &RunContext) -> Result<Answer> {
+        let input = self.parsed.as_ref().context(NOT_PARSED)?;
+
+        let name = match context.implementation() {
+            None | Some(DEFAULT_IMPLEMENTATION) => {
+                return match part {
+                    1 => self.solution.part1(input, context),
+                    _ => self.solution.part2(input, context),
+                };
+            }
+            Some(name) => name,
+        };
+
+        let implementations = self.solution.implementations();
+        let Some(implementation) = implementations
+            .iter()
+            .find(|implementation| implementation.part == part && implementation.name == name)
+        else {
+            anyhow::bail!(
+                "part {} has no implementation {:?}, only {}",
+                part,
+                name,
+                DynSolution::implementations(self, part).join(", ")
+            );
+        };
+
+        (implementation.run)(&mut self.solution, input, context)
+    }
+}
+
+impl<S: Solution + Send> DynSolution for Erased<S>
+where
+    S::Parsed: Send,
+{
+    fn parse(&mut self, input: &str) -> Result<()> {
+        self.parsed = Some(self.solution.parse(input)?);
+        Ok(())
+    }
+
+    fn part1(&mut self, context: &RunContext) -> Result<Answer> {
+        self.run(1, context)
+    }
+
+    fn part2(&mut self, context: &RunContext) -> Result<Answer> {
+        self.run(2, context)
+    }
+
+    fn known_solution_part1(&self) -> Option<Answer> {
+        self.solution.known_solution_part1()
+    }
+
+    fn known_solution_part2(&self) -> Option<Answer> {
+        self.solution.known_solution_part2()
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        self.solution.examples()
+    }
+
+    fn implementations(&self, part: u8) -> Vec<&'static str> {
+        std::iter::once(DEFAULT_IMPLEMENTATION)
+            .chain(
+                self.solution
+                    .implementations()
+                    .into_iter()
+                    .filter(|implementation| implementation.part == part)
+                    .map(|implementation| implementation.name),
+            )
+            .collect()
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        self.solution.generate(seed, size)
+    }
+
+    fn generated_params(&self, size: usize) -> Vec<(String, String)> {
+        self.solution.generated_params(size)
+    }
+}
+
+/// A registered solution, collected at link time via [`inventory`].
+pub struct SolutionInfo {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    /// The day's source file, whose module docs hold the puzzle text and examples
+    pub source: &'static str,
+    pub normalize: Normalize,
+    pub new: fn() -> Box<dyn DynSolution>,
+}
+
+inventory::collect!(SolutionInfo);
+
+/// Registers a [`Solution`] so `get_solution` and `list_solutions` can find it.
+macro_rules! register_solution {
+    ($solution:ty, year = $year:literal, day = $day:literal, title = $title:literal) => {
+        inventory::submit! {
+            $crate::SolutionInfo {
+                year: $year,
+                day: $day,
+                title: $title,
+                source: include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", file!())),
+                normalize: <$solution as $crate::Solution>::NORMALIZE,
+                new: || Box::new($crate::Erased::<$solution>::new()),
+            }
+        }
+    };
+}
+
+pub mod alloc_count;
+pub mod answer;
+pub mod budget;
+pub mod context;
+pub mod direction;
+pub mod examples;
+pub mod generate;
+pub mod grid;
+pub mod input;
+pub mod known_answers;
+pub mod output;
+pub mod runner;
+pub mod scaffold;
+pub mod timing;
+pub mod watch;
+pub mod y2025;
+
+/// All registered solutions, ordered by year then day.
+pub fn list_solutions() -> Vec<&'static SolutionInfo> {
+    let mut solutions = inventory::iter::<SolutionInfo>
+        .into_iter()
+        .collect::<Vec<_>>();
+    solutions.sort_by_key(|info| (info.year, info.day));
+    solutions
+}
+
+/// The registered solutions for one year, ordered by day.
+pub fn list_year(year: u16) -> Vec<&'static SolutionInfo> {
+    list_solutions()
+        .into_iter()
+        .filter(|info| info.year == year)
+        .collect()
+}
+
+pub fn get_solution_info(year: u16, day: u8) -> Result<&'static SolutionInfo> {
+    list_solutions()
+        .into_iter()
+        .find(|info| info.year == year && info.day == day)
+        .ok_or_else(|| anyhow::anyhow!("No solution registered for day {} of {}", day, year))
+}
+
+pub fn get_solution(year: u16, day: u8) -> Result<Box<dyn DynSolution>> {
+    get_solution_info(year, day).map(|info| (info.new)())
+}
+
+/// The year of the latest Advent of Code: the current year from December, when the
+/// puzzles start, and the year before until then.
+pub fn current_year() -> u16 {
+    let days = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs() / 86_400);
+
+    event_year(days as i64)
+}
+
+fn event_year(days: i64) -> u16 {
+    // Civil date from days since 1970-01-01, see
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    // Months are counted from March, so January and February belong to the next year
+    let year = year_of_era + era * 400 + i64::from(month_index >= 10);
+    let december = month_index == 9;
+
+    (if december { year } else { year - 1 }) as u16
+}
+
+/// The file name of a day's real input, which is also its key in the known answers file.
+pub fn input_name(day: u8) -> String {
+    format!("{}_input.txt", day)
+}
+
+pub fn get_input(year: u16, day: u8) -> Result<String> {
+    // File path is in inputs/{year}/{day}_input.txt, or under AOC_INPUT_DIR if set, with
+    // the copy built in by the `embed-inputs` feature as a fallback
+    input::InputSource::Real.read(year, day)
+}
+
+/// Parses each line of `input`, reporting the line number and contents of the first failure.
+pub fn parse_lines<T>(input: &str, mut parse: impl FnMut(&str) -> Result<T>) -> Result<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).with_context(|| format!("line {}: {:?}", i + 1, line)))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_registry() {
+        let days = list_solutions()
+            .iter()
+            .map(|info| (info.year, info.day))
+            .collect::<Vec<_>>();
+
+        // Sorted by year then day, each registered once
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!((1..=12).all(|day| days.contains(&(2025, day))));
+        assert!(get_solution(2025, 1).is_ok());
+        assert!(get_solution(2025, 25).is_err());
+        assert!(get_solution(2015, 1).is_err());
+    }
+
+    #[test]
+    fn test_event_year() {
+        // Days since 1970-01-01
+        assert_eq!(event_year(20423), 2025); // 2025-12-01
+        assert_eq!(event_year(20422), 2024); // 2025-11-30
+        assert_eq!(event_year(20468), 2025); // 2026-01-15
+        assert_eq!(event_year(20744), 2025); // 2026-10-18
+    }
+
+    #[test]
+    fn test_examples() {
+        for info in list_solutions() {
+            for report in runner::run_examples(info, &Default::default(), &Default::default()) {
+                for part in report.parts.iter().filter(|part| part.expected.is_some()) {
+                    assert!(
+                        !part.is_failure(),
+                        "Part {} of day {} failed on {}: {:?}",
+                        part.part,
+                        info.day,
+                        report.input_name,
+                        part.answer
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_implementations() {
+        for info in list_solutions() {
+            let blocks = examples::doc_blocks(info.source);
+
+            for example in (info.new)().examples() {
+                let input = info.normalize.apply(&blocks[example.block]);
+                let overrides = RunContext::example().with_params(&example.params);
+                let comparisons = runner::run_comparison(
+                    info,
+                    &input,
+                    &Default::default(),
+                    &overrides,
+                    |_, part| example.expected(part),
+                )
+                .unwrap();
+
+                for comparison in comparisons {
+                    assert!(
+                        !comparison.is_failure(),
+                        "Part {} of day {} disagrees on example {}: {:?}",
+                        comparison.part,
+                        info.day,
+                        example.block,
+                        comparison.implementations
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_generators() {
+        for info in list_solutions() {
+            let mut solution = (info.new)();
+            let Some(input) = solution.generate(7, 20) else {
+                continue;
+            };
+
+            assert_eq!(solution.generate(7, 20).as_ref(), Some(&input));
+            assert_ne!(solution.generate(8, 20).as_ref(), Some(&input));
+            solution.parse(&info.normalize.apply(&input)).unwrap();
+
+            let report =
+                runner::run_generated(info, 7, 20, &Default::default(), &Default::default())
+                    .unwrap();
+            for part in &report.parts {
+                assert!(
+                    !part.is_failure(),
+                    "Part {} of day {} failed on a generated input: {:?}",
+                    part.part,
+                    info.day,
+                    part.answer
+                );
+            }
+        }
+    }
+
     #[ignore]
     #[test]
     fn test_all_parts() {
-        for day in 1..=25 {
-            let mut solution = get_solution(day);
-            let input = get_input(day);
-            let input = input.trim();
+        for info in list_solutions() {
+            let day = info.day;
+            let mut solution = (info.new)();
+            let input = info.normalize.apply(&get_input(info.year, day).unwrap());
 
-            let part1 = solution.part1(input);
-            let part2 = solution.part2(input);
+            solution.parse(&input).unwrap();
+            let part1 = solution.part1(&RunContext::real()).unwrap();
+            let part2 = solution.part2(&RunContext::real()).unwrap();
 
             if let Some(known_solution_part1) = solution.known_solution_part1() {
                 assert_eq!(
@@ -1,52 +1,448 @@
-use aoc2025::{get_input, get_solution};
-use clap::Parser;
+use std::{ops::Range, path::Path, time::Duration};
+
+use anyhow::bail;
+use aoc2025::{
+    RunContext, budget,
+    context::parse_param,
+    current_year,
+    generate::DEFAULT_SIZE,
+    get_solution_info,
+    input::InputSource,
+    known_answers::{ANSWERS_FILE, KnownAnswers},
+    list_year,
+    output::{Format, Output},
+    runner::{
+        Comparison, expected_answer, for_each_day, print_comparison, print_part, run_comparison,
+        run_day, run_examples, run_generated,
+    },
+    scaffold::new_day,
+    timing::{CpuClock, RunOptions, Stopwatch, parse_duration},
+    watch::{SCRATCH_INPUT, Watcher},
+};
+use clap::{Parser, Subcommand};
+use itertools::Itertools;
 
 #[derive(Parser)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, subcommand_negates_reqs = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
     /// What day of the AoC is this?
-    num: u8,
+    #[arg(required = true)]
+    num: Option<u8>,
+    /// Which year's solutions to run, defaulting to the latest Advent of Code
+    #[arg(short, long, default_value_t = current_year(), global = true)]
+    year: u16,
     /// Whether to time the solution parts
-    #[arg(short, long, default_value_t = false)]
+    #[arg(short, long, default_value_t = false, global = true)]
     time: bool,
+    /// Input to run on: a file path, `-` for stdin, or a name like `example` for
+    /// `inputs/{day}_example.txt` (the directory can be changed with AOC_INPUT_DIR)
+    #[arg(short, long, global = true)]
+    input: Option<InputSource>,
+    /// Record the computed answers as known answers in answers.toml
+    #[arg(long, default_value_t = false, global = true)]
+    record: bool,
+    /// Run the examples from the puzzle text instead of the real input
+    #[arg(short, long, default_value_t = false, global = true, conflicts_with_all = ["input", "record"])]
+    example: bool,
+    /// How many times to run each part, reporting min/median/mean/p95 when more than one
+    #[arg(long, default_value_t = 1, global = true, value_parser = clap::value_parser!(u64).range(1..))]
+    runs: u64,
+    /// Unmeasured runs before the measured ones, defaulting to 1 when --runs is above 1
+    #[arg(long, global = true)]
+    warmup: Option<u64>,
+    /// Solve days in parallel when running more than one, still printing them in order
+    #[arg(short = 'j', long, default_value_t = false, global = true)]
+    parallel: bool,
+    /// Only run this part
+    #[arg(short, long, global = true, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Give up on a part after this long, e.g. `30s` or `500ms`. A part that times out
+    /// keeps running in the background until the program exits, so it can skew the timings
+    /// of everything run after it
+    #[arg(long, global = true, value_parser = parse_duration)]
+    timeout: Option<Duration>,
+    /// Override an iteration budget's limit, e.g. `queue=500`; can be given more than once
+    #[arg(long = "budget", value_name = "NAME=LIMIT", global = true, value_parser = budget::parse_limit)]
+    budgets: Vec<(String, u64)>,
+    /// Set a parameter the day reads, e.g. `connections=10`; can be given more than once
+    #[arg(long = "param", value_name = "NAME=VALUE", global = true, value_parser = parse_param)]
+    params: Vec<(String, String)>,
+    /// Run this implementation of a single day's parts rather than the default one
+    #[arg(long = "impl", value_name = "NAME", global = true)]
+    implementation: Option<String>,
+    /// Re-run the day whenever its input, or `{day}_scratch.txt` next to it, changes
+    #[arg(short, long, default_value_t = false, conflicts_with_all = ["record", "example", "parallel", "timeout"])]
+    watch: bool,
+    /// How to print results: text, json or csv
+    #[arg(long, default_value = "text", global = true)]
+    format: Format,
+}
+
+impl Cli {
+    fn run_options(&self) -> RunOptions {
+        let runs = self.runs as usize;
+        let warmup = self
+            .warmup
+            .map_or(if runs > 1 { 1 } else { 0 }, |warmup| warmup as usize);
+
+        RunOptions {
+            runs,
+            warmup,
+            part: self.part,
+            timeout: self.timeout,
+        }
+    }
+
+    /// What the command line sets for the solutions, over what examples set themselves.
+    fn overrides(&self) -> RunContext {
+        let overrides = RunContext::default().with_params(&self.params);
+        match &self.implementation {
+            Some(name) => overrides.with_implementation(name),
+            None => overrides,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run every registered day and print a summary table
+    All,
+    /// Create a new day's source file and empty input, and register its module
+    New {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// The puzzle's title
+        #[arg(long, default_value = "TODO")]
+        title: String,
+    },
+    /// Run every implementation of a day's parts on its input, checking that they agree
+    /// and comparing their times
+    Compare {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Print a random input for a day from its generator
+    Gen {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Roughly how many lines or items the input has
+        #[arg(long, default_value_t = DEFAULT_SIZE)]
+        size: usize,
+    },
+    /// Run a day on many generated inputs, reporting the seeds it errors or panics on
+    Stress {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// How many seeds to try
+        #[arg(long, default_value_t = 100)]
+        seeds: u64,
+        /// The seed to start from
+        #[arg(long, default_value_t = 0)]
+        first_seed: u64,
+        /// Roughly how many lines or items each input has
+        #[arg(long, default_value_t = DEFAULT_SIZE)]
+        size: usize,
+    },
 }
 
-fn main() {
+fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
-    let mut start_part1 = None;
-    let mut start_part2 = None;
+    let mut known = KnownAnswers::load(ANSWERS_FILE)?;
+    let source = cli.input.clone().unwrap_or_default();
+    let options = cli.run_options();
+    let overrides = cli.overrides();
 
-    let input = get_input(cli.num);
-    let input = input.trim();
-    let mut solution = get_solution(cli.num);
+    for (name, limit) in &cli.budgets {
+        budget::set_limit(name, *limit);
+    }
 
-    if cli.time {
-        start_part1 = Some(std::time::Instant::now());
+    match &cli.command {
+        Some(Command::New { day, title }) => {
+            for path in new_day(Path::new("."), cli.year, *day, title)? {
+                println!("Wrote {}", path.display());
+            }
+            return Ok(());
+        }
+        Some(Command::Gen { day, seed, size }) => {
+            let info = get_solution_info(cli.year, *day)?;
+            let solution = (info.new)();
+            let Some(input) = solution.generate(*seed, *size) else {
+                bail!("Day {} of {} has no input generator", day, cli.year);
+            };
+            println!("{}", input);
+            for (name, value) in solution.generated_params(*size) {
+                eprintln!("Run it with --param {}={}", name, value);
+            }
+            return Ok(());
+        }
+        Some(Command::Stress {
+            day,
+            seeds,
+            first_seed,
+            size,
+        }) => {
+            return stress(&cli, *day, *first_seed..first_seed + seeds, *size, &options);
+        }
+        Some(Command::Compare { day }) => {
+            return compare(&cli, &known, *day, &source, &options);
+        }
+        Some(Command::All) | None => {}
     }
 
-    let part1 = solution.part1(input);
-    if let Some(known_part1) = solution.known_solution_part1() {
-        assert_eq!(part1, known_part1);
+    if cli.watch {
+        return watch(&cli, &known, &source, &options);
     }
 
-    if let Some(start_part1) = start_part1 {
-        println!("Part 1 took {:?}", start_part1.elapsed());
+    // Everything except a single day printed for people goes through the day reports
+    if cli.example || cli.command.is_some() || cli.format != Format::Text {
+        let infos = match (&cli.command, cli.num) {
+            (Some(Command::All), _) => list_year(cli.year),
+            (
+                Some(
+                    Command::New { .. }
+                    | Command::Compare { .. }
+                    | Command::Gen { .. }
+                    | Command::Stress { .. },
+                ),
+                _,
+            ) => {
+                unreachable!("handled above")
+            }
+            (None, Some(num)) => vec![get_solution_info(cli.year, num)?],
+            (None, None) => unreachable!("clap requires a day when no subcommand is given"),
+        };
+
+        if cli.command.is_some() && !source.is_per_day() {
+            bail!("--input with a file or stdin only works for a single day");
+        }
+        if cli.command.is_some() && cli.implementation.is_some() {
+            bail!("--impl only works for a single day, since each day names its own");
+        }
+        if infos.is_empty() {
+            bail!("No solutions registered for {}", cli.year);
+        }
+
+        // Workers look up expected answers in a snapshot, since recording updates `known`
+        let expected = known.clone();
+        let stopwatch = Stopwatch::start(CpuClock::Process);
+        let mut output = Output::new(cli.format, options, cli.parallel);
+        let mut reports = vec![];
+        let mut result = Ok(());
+
+        for_each_day(
+            &infos,
+            cli.parallel,
+            |info| {
+                if cli.example {
+                    run_examples(info, &options, &overrides)
+                } else {
+                    vec![run_day(
+                        info,
+                        &expected,
+                        &source,
+                        &options,
+                        &overrides,
+                        |_| {},
+                    )]
+                }
+            },
+            |day_reports| {
+                for report in day_reports {
+                    if result.is_ok() {
+                        result = output.day(&report);
+                    }
+
+                    // Save as we go so a day that aborts the process doesn't lose earlier answers
+                    if cli.record && result.is_ok() {
+                        report.record(&mut known);
+                        result = known.save(ANSWERS_FILE);
+                    }
+
+                    reports.push(report);
+                }
+            },
+        );
+
+        result?;
+        output.finish(&reports, stopwatch.elapsed())?;
+
+        if reports.iter().any(|report| report.is_failure()) {
+            std::process::exit(1);
+        }
+
+        return Ok(());
     }
 
-    println!("Part 1: {}", part1);
+    let num = cli
+        .num
+        .expect("clap requires a day when no subcommand is given");
+    let show_time = cli.time || options.runs > 1;
 
-    if cli.time {
-        start_part2 = Some(std::time::Instant::now());
+    let info = get_solution_info(cli.year, num)?;
+    // Recording replaces the recorded answers, so only check against hard-coded ones
+    let none_recorded = KnownAnswers::default();
+    let checked = if cli.record { &none_recorded } else { &known };
+
+    let report = run_day(info, checked, &source, &options, &overrides, |report| {
+        let Some(part) = report.parts.last() else {
+            if show_time {
+                println!("Input took {:.2?}", report.input_time);
+                println!("Parsing took {}", report.parse_time);
+            }
+            return;
+        };
+
+        // A part that failed or timed out has no timings worth showing
+        if show_time && part.answer.is_ok() && part.time.runs > 0 {
+            println!("Part {} took {}", part.part, part.time);
+        }
+        if let Some(alloc) = part.alloc {
+            println!("Part {} made {}", part.part, alloc);
+        }
+        print_part(part);
+    });
+
+    if cli.record {
+        report.record(&mut known);
+        known.save(ANSWERS_FILE)?;
     }
 
-    let part2 = solution.part2(input);
-    if let Some(known_part2) = solution.known_solution_part2() {
-        assert_eq!(part2, known_part2);
+    if report.is_failure() {
+        std::process::exit(1);
     }
 
-    if let Some(start_part2) = start_part2 {
-        println!("Part 2 took {:?}", start_part2.elapsed());
+    Ok(())
+}
+
+/// Runs every implementation of a day's parts on one input and prints how they compare.
+fn compare(
+    cli: &Cli,
+    known: &KnownAnswers,
+    day: u8,
+    source: &InputSource,
+    options: &RunOptions,
+) -> anyhow::Result<()> {
+    let info = get_solution_info(cli.year, day)?;
+    let input = info.normalize.apply(&source.read(cli.year, day)?);
+
+    let comparisons = run_comparison(info, &input, options, &cli.overrides(), |solution, part| {
+        expected_answer(known, solution, cli.year, day, source, part)
+    })
+    .map_err(|e| anyhow::anyhow!("Parsing failed: {}", e))?;
+
+    for comparison in &comparisons {
+        print_comparison(comparison);
+    }
+
+    if comparisons.iter().any(Comparison::is_failure) {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Runs a day on the inputs generated from `seeds`, printing every part that fails.
+fn stress(
+    cli: &Cli,
+    day: u8,
+    seeds: Range<u64>,
+    size: usize,
+    options: &RunOptions,
+) -> anyhow::Result<()> {
+    let info = get_solution_info(cli.year, day)?;
+    // Failures are printed below with their seed, so panics shouldn't print their own
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let mut failed = vec![];
+    for seed in seeds.clone() {
+        let Some(report) = run_generated(info, seed, size, options, &cli.overrides()) else {
+            bail!("Day {} of {} has no input generator", day, cli.year);
+        };
+
+        for part in &report.parts {
+            if let Err(e) = &part.answer {
+                println!("Seed {}: part {} failed: {}", seed, part.part, e);
+            }
+        }
+        if report.is_failure() {
+            failed.push(seed);
+        }
     }
 
-    println!("Part 2: {}", part2);
+    println!("{} seeds, {} failed", seeds.end - seeds.start, failed.len());
+    if let Some(seed) = failed.first() {
+        println!(
+            "Reproduce with `gen {} --year {} --seed {} --size {}`",
+            day, cli.year, seed, size
+        );
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Re-runs one day on its input, and its scratch input if there is one, whenever either
+/// file changes. Runs until interrupted.
+fn watch(
+    cli: &Cli,
+    known: &KnownAnswers,
+    source: &InputSource,
+    options: &RunOptions,
+) -> anyhow::Result<()> {
+    let Some(num) = cli.num else {
+        bail!("--watch only works for a single day");
+    };
+    let info = get_solution_info(cli.year, num)?;
+
+    let scratch = InputSource::Named(SCRATCH_INPUT.to_string());
+    let mut sources = vec![source.clone()];
+    if *source != scratch {
+        sources.push(scratch);
+    }
+
+    let Some(paths) = sources
+        .iter()
+        .map(|source| source.path(cli.year, num))
+        .collect::<Option<Vec<_>>>()
+    else {
+        bail!("--watch needs an input file to watch, not stdin");
+    };
+    let mut watcher = Watcher::new(paths.clone());
+
+    loop {
+        watcher.wait();
+
+        // Clear the screen and move the cursor to the top left
+        print!("\x1b[2J\x1b[H");
+        println!(
+            "Day {} of {}, watching {}",
+            num,
+            cli.year,
+            paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .join(", ")
+        );
+
+        let stopwatch = Stopwatch::start(CpuClock::Process);
+        let mut output = Output::new(cli.format, *options, false);
+        let mut reports = vec![];
+
+        for (source, path) in sources.iter().zip(&paths) {
+            // The scratch input is optional, unlike the one asked for
+            if *source != sources[0] && !path.exists() {
+                continue;
+            }
+
+            let report = run_day(info, known, source, options, &cli.overrides(), |_| {});
+            output.day(&report)?;
+            reports.push(report);
+        }
+
+        output.finish(&reports, stopwatch.elapsed())?;
+    }
 }
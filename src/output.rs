@@ -0,0 +1,208 @@
+use std::{io, time::Duration};
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+
+use crate::{
+    runner::{
+        DayReport, Status, print_run_time, print_summary_header, print_summary_rows,
+        print_summary_total,
+    },
+    timing::{Elapsed, RunOptions},
+};
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// A table meant for people
+    #[default]
+    Text,
+    /// One JSON array of [`PartRecord`]s once everything has run
+    Json,
+    /// One CSV row per [`PartRecord`], written as each day finishes
+    Csv,
+}
+
+impl std::str::FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "text" => Format::Text,
+            "json" => Format::Json,
+            "csv" => Format::Csv,
+            _ => bail!("unknown format {:?}, expected text, json or csv", s),
+        })
+    }
+}
+
+/// One part's result as it appears in JSON and CSV output.
+///
+/// Fields are only ever added to the end, so scripts can rely on the rest. Answers are
+/// strings so integers too large for a double survive, and times are in nanoseconds.
+#[derive(Debug, Serialize)]
+pub struct PartRecord<'a> {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'a str,
+    pub input: &'a str,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    pub error: Option<&'a str>,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub p95_ns: u64,
+    /// Shared by both parts of the day
+    pub parse_ns: u64,
+    /// Shared by both parts of the day
+    pub input_ns: u64,
+    /// Allocation counts are only present with the `count-allocations` feature
+    pub allocations: Option<u64>,
+    pub allocated_bytes: Option<u64>,
+    pub peak_bytes: Option<u64>,
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+pub fn records(report: &DayReport) -> impl Iterator<Item = PartRecord<'_>> {
+    report.parts.iter().map(|part| PartRecord {
+        year: report.year,
+        day: report.day,
+        title: report.title,
+        input: &report.input_name,
+        part: part.part,
+        status: part.status(),
+        answer: part
+            .answer
+            .as_ref()
+            .ok()
+            .filter(|answer| !answer.is_unsolved())
+            .map(ToString::to_string),
+        expected: part.expected.as_ref().map(ToString::to_string),
+        error: part.answer.as_ref().err().map(String::as_str),
+        runs: part.time.runs,
+        min_ns: nanos(part.time.min),
+        median_ns: nanos(part.time.median),
+        mean_ns: nanos(part.time.mean),
+        p95_ns: nanos(part.time.p95),
+        parse_ns: nanos(report.parse_time.median),
+        input_ns: nanos(report.input_time),
+        allocations: part.alloc.map(|alloc| alloc.allocations),
+        allocated_bytes: part.alloc.map(|alloc| alloc.bytes),
+        peak_bytes: part.alloc.map(|alloc| alloc.peak),
+    })
+}
+
+/// Prints day reports in the chosen [`Format`] as they come in.
+pub struct Output {
+    format: Format,
+    options: RunOptions,
+    /// Whether days overlapped, making each day's own wall and CPU time worth showing
+    parallel: bool,
+    csv: Option<csv::Writer<io::Stdout>>,
+}
+
+impl Output {
+    pub fn new(format: Format, options: RunOptions, parallel: bool) -> Self {
+        if format == Format::Text {
+            print_summary_header(&options);
+        }
+
+        Self {
+            format,
+            options,
+            parallel,
+            csv: (format == Format::Csv).then(|| csv::Writer::from_writer(io::stdout())),
+        }
+    }
+
+    pub fn day(&mut self, report: &DayReport) -> Result<()> {
+        match self.format {
+            Format::Text => {
+                print_summary_rows(report, &self.options);
+                if self.parallel {
+                    print_run_time(report);
+                }
+            }
+            Format::Json => {}
+            Format::Csv => {
+                let writer = self.csv.as_mut().expect("CSV output has a writer");
+                for record in records(report) {
+                    writer.serialize(record)?;
+                }
+                writer.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prints whatever comes after every day, given all of their reports.
+    pub fn finish(self, reports: &[DayReport], run_time: Elapsed) -> Result<()> {
+        match self.format {
+            Format::Text => {
+                print_summary_total(reports);
+                if self.parallel {
+                    println!("Ran in {}", run_time);
+                }
+            }
+            Format::Json => {
+                let records = reports.iter().flat_map(records).collect::<Vec<_>>();
+                serde_json::to_writer_pretty(io::stdout(), &records)?;
+                println!();
+            }
+            Format::Csv => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Answer, runner::PartReport, timing::Stats};
+
+    #[test]
+    fn test_records() {
+        let report = DayReport {
+            year: 2025,
+            day: 3,
+            title: "Lobby",
+            input_name: "3_input.txt".to_string(),
+            input_time: Duration::from_micros(2),
+            parse_time: Stats::default(),
+            parts: vec![
+                PartReport {
+                    part: 1,
+                    answer: Ok(Answer::from(u64::MAX)),
+                    expected: Some(Answer::from(u64::MAX)),
+                    time: Stats::default(),
+                    alloc: None,
+                },
+                PartReport {
+                    part: 2,
+                    answer: Err("line 1: bad".to_string()),
+                    expected: None,
+                    time: Stats::default(),
+                    alloc: None,
+                },
+            ],
+            run_time: Elapsed::default(),
+        };
+
+        let json = serde_json::to_value(records(&report).collect::<Vec<_>>()).unwrap();
+        assert_eq!(json[0]["status"], "correct");
+        assert_eq!(json[0]["answer"], "18446744073709551615");
+        assert_eq!(json[0]["input_ns"], 2000);
+        assert_eq!(json[1]["status"], "failed");
+        assert_eq!(json[1]["answer"], serde_json::Value::Null);
+        assert_eq!(json[1]["error"], "line 1: bad");
+    }
+}
@@ -0,0 +1,758 @@
+use std::{
+    collections::BTreeMap,
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+use colored::Colorize;
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::{
+    Answer, DynSolution, RunContext, SolutionInfo,
+    alloc_count::{self, AllocStats, track},
+    budget,
+    examples::doc_blocks,
+    input::InputSource,
+    known_answers::KnownAnswers,
+    timing::{CpuClock, Elapsed, RunOptions, Stats, Stopwatch, measure},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// Matched the known solution
+    Correct,
+    /// Did not match the known solution
+    Wrong,
+    /// No known solution to compare against
+    Unchecked,
+    /// The part hasn't been implemented yet
+    Unsolved,
+    /// The part returned an error or panicked
+    Failed,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Correct => write!(f, "{}", "✓".green()),
+            Status::Wrong => write!(f, "{}", "✗".red()),
+            Status::Unchecked => write!(f, "{}", "?".yellow()),
+            Status::Unsolved => write!(f, "{}", "-".dimmed()),
+            Status::Failed => write!(f, "{}", "!".red()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub part: u8,
+    /// The answer, or the error chain or panic message if the part failed
+    pub answer: Result<Answer, String>,
+    pub expected: Option<Answer>,
+    pub time: Stats,
+    /// What the last run allocated, with the `count-allocations` feature
+    pub alloc: Option<AllocStats>,
+}
+
+impl PartReport {
+    pub fn status(&self) -> Status {
+        match (&self.answer, &self.expected) {
+            (Err(_), _) => Status::Failed,
+            (Ok(Answer::Unsolved), _) => Status::Unsolved,
+            (Ok(_), None) => Status::Unchecked,
+            (Ok(answer), Some(expected)) if answer == expected => Status::Correct,
+            (Ok(_), Some(_)) => Status::Wrong,
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status(), Status::Wrong | Status::Failed)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    /// The known answers key of the input this day ran on
+    pub input_name: String,
+    /// How long reading and normalizing the input took, which isn't counted in the parts
+    pub input_time: Duration,
+    /// Parsing happens once and is shared by both parts, so it's timed on its own
+    pub parse_time: Stats,
+    pub parts: Vec<PartReport>,
+    /// Everything the day did, including warmups and repeated runs, on the thread it ran on
+    pub run_time: Elapsed,
+}
+
+impl DayReport {
+    /// Stores every solved answer as known for this day's input.
+    pub fn record(&self, known: &mut KnownAnswers) {
+        for part in &self.parts {
+            if let Ok(answer) = &part.answer {
+                known.record(self.year, self.day, &self.input_name, part.part, answer);
+            }
+        }
+    }
+
+    /// The median parse time plus each part's median time
+    pub fn elapsed(&self) -> Duration {
+        self.parse_time.median
+            + self
+                .parts
+                .iter()
+                .map(|part| part.time.median)
+                .sum::<Duration>()
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.parts.iter().any(PartReport::is_failure)
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panicked".to_string()
+    }
+}
+
+/// The expected answer for a part, preferring the known answers file over answers
+/// hard-coded in the solution, which only apply to the day's real input from the default
+/// input directory, see [`InputSource::is_own_input`].
+pub fn expected_answer(
+    known: &KnownAnswers,
+    solution: &dyn DynSolution,
+    year: u16,
+    day: u8,
+    source: &InputSource,
+    part: u8,
+) -> Option<Answer> {
+    known
+        .get(year, day, &source.name(year, day), part)
+        .or_else(|| {
+            if !source.is_own_input() {
+                return None;
+            }
+
+            match part {
+                1 => solution.known_solution_part1(),
+                _ => solution.known_solution_part2(),
+            }
+        })
+}
+
+/// Runs `f`, turning errors and panics into messages so one broken day doesn't stop
+/// the others.
+fn catch<T>(f: impl FnOnce() -> anyhow::Result<T>) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .map_err(panic_message)
+        .and_then(|result| result.map_err(|e| format!("{:#}", e)))
+}
+
+/// Parses `input` as many times as `options` asks, keeping the last result for the parts.
+pub fn run_parse(
+    solution: &mut dyn DynSolution,
+    input: &str,
+    options: &RunOptions,
+) -> (Result<(), String>, Stats) {
+    measure(options, || {
+        budget::reset_all();
+        catch(|| solution.parse(input))
+    })
+}
+
+/// A new solution with `input` parsed, for when a part that timed out kept the old one.
+pub fn fresh_solution(info: &SolutionInfo, input: &str) -> Box<dyn DynSolution> {
+    let mut solution = (info.new)();
+    // Parsing already succeeded once, and was timed then
+    let _ = catch(|| solution.parse(input));
+    solution
+}
+
+/// Runs one part on the parsed input as many times as `options` asks. The answer is
+/// from the last run.
+///
+/// With a timeout the part runs on a worker thread, and the CPU time it used there is
+/// returned too, since the caller's thread clock doesn't see it. Threads can't be
+/// stopped, so one that times out is left running with the solution, using CPU time
+/// nobody counts, and `solution` is left empty.
+pub fn run_part(
+    solution: &mut Option<Box<dyn DynSolution>>,
+    part: u8,
+    options: &RunOptions,
+    context: &RunContext,
+) -> (Result<Answer, String>, Stats, Option<AllocStats>, Duration) {
+    let Some(timeout) = options.timeout else {
+        let solution = solution.as_deref_mut().expect("a solution to run");
+        let (answer, time, alloc) = run_part_here(solution, part, options, context);
+        return (answer, time, alloc, Duration::ZERO);
+    };
+
+    let mut worker_solution = solution.take().expect("a solution to run");
+    let (sender, receiver) = mpsc::channel();
+    let options = *options;
+    let context = context.clone();
+
+    let spawned = std::thread::Builder::new()
+        .name(format!("part {}", part))
+        .spawn(move || {
+            let stopwatch = Stopwatch::start(CpuClock::Thread);
+            let result = run_part_here(worker_solution.as_mut(), part, &options, &context);
+            let cpu = stopwatch.elapsed().cpu.unwrap_or_default();
+            // The receiver is gone if we stopped waiting, and nobody needs the result
+            let _ = sender.send((worker_solution, result, cpu));
+        });
+    if let Err(e) = spawned {
+        return (
+            Err(format!("failed to start a worker thread: {}", e)),
+            Stats::default(),
+            None,
+            Duration::ZERO,
+        );
+    }
+
+    match receiver.recv_timeout(timeout) {
+        Ok((returned, (answer, time, alloc), cpu)) => {
+            *solution = Some(returned);
+            (answer, time, alloc, cpu)
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => (
+            Err(format!("timed out after {:.2?}", timeout)),
+            Stats::default(),
+            None,
+            Duration::ZERO,
+        ),
+        Err(mpsc::RecvTimeoutError::Disconnected) => (
+            Err("the worker thread died".to_string()),
+            Stats::default(),
+            None,
+            Duration::ZERO,
+        ),
+    }
+}
+
+fn run_part_here(
+    solution: &mut dyn DynSolution,
+    part: u8,
+    options: &RunOptions,
+    context: &RunContext,
+) -> (Result<Answer, String>, Stats, Option<AllocStats>) {
+    let mut alloc = None;
+    let (answer, time) = measure(options, || {
+        budget::reset_all();
+        let (answer, stats) = track(|| {
+            catch(|| match part {
+                1 => solution.part1(context),
+                _ => solution.part2(context),
+            })
+        });
+        alloc = stats;
+        answer
+    });
+
+    (answer, time, alloc)
+}
+
+/// Reads an input and normalizes it for the day, timing how long that took.
+fn load_input(
+    info: &SolutionInfo,
+    read: impl FnOnce() -> Result<String, String>,
+) -> (Result<String, String>, Duration) {
+    let start = Instant::now();
+    let input = read().map(|input| info.normalize.apply(&input));
+    (input, start.elapsed())
+}
+
+/// Runs both parts of a day on `input`, showing `progress` the report so far once the
+/// input is parsed and again after each part.
+fn run_parts(
+    info: &SolutionInfo,
+    input_name: String,
+    (input, input_time): (Result<String, String>, Duration),
+    options: &RunOptions,
+    context: &RunContext,
+    expected: impl Fn(&dyn DynSolution, u8) -> Option<Answer>,
+    mut progress: impl FnMut(&DayReport),
+) -> DayReport {
+    let stopwatch = Stopwatch::start(CpuClock::Thread);
+    let mut first = (info.new)();
+
+    let (parsed, parse_time) = match &input {
+        Ok(input) => run_parse(first.as_mut(), input, options),
+        Err(e) => (Err(e.clone()), Stats::default()),
+    };
+
+    let mut report = DayReport {
+        year: info.year,
+        day: info.day,
+        title: info.title,
+        input_name,
+        input_time,
+        parse_time,
+        parts: vec![],
+        run_time: Elapsed::default(),
+    };
+    progress(&report);
+
+    let mut solution = Some(first);
+    let mut worker_cpu = Duration::ZERO;
+
+    for part in options.parts() {
+        // A part that timed out kept the solution, so later parts get a freshly parsed one
+        let current = solution.get_or_insert_with(|| match &input {
+            Ok(input) => fresh_solution(info, input),
+            Err(_) => (info.new)(),
+        });
+        let expected = expected(current.as_ref(), part);
+
+        let (answer, time, alloc, cpu) = match &parsed {
+            Ok(()) => run_part(&mut solution, part, options, context),
+            Err(e) => (Err(e.clone()), Stats::default(), None, Duration::ZERO),
+        };
+        worker_cpu += cpu;
+
+        report.parts.push(PartReport {
+            part,
+            answer,
+            expected,
+            time,
+            alloc,
+        });
+        progress(&report);
+    }
+
+    report.run_time = stopwatch.elapsed().with_cpu(worker_cpu);
+    report
+}
+
+/// Runs a day on a real input, with `overrides` from the command line. `progress` sees
+/// the report as it's filled in, see [`run_parts`].
+pub fn run_day(
+    info: &SolutionInfo,
+    known: &KnownAnswers,
+    source: &InputSource,
+    options: &RunOptions,
+    overrides: &RunContext,
+    progress: impl FnMut(&DayReport),
+) -> DayReport {
+    let input_name = source.name(info.year, info.day);
+    let input = load_input(info, || {
+        source
+            .read(info.year, info.day)
+            .map_err(|e| format!("{:#}", e))
+    });
+
+    run_parts(
+        info,
+        input_name.clone(),
+        input,
+        options,
+        &RunContext::real().overridden_by(overrides),
+        |solution, part| expected_answer(known, solution, info.year, info.day, source, part),
+        progress,
+    )
+}
+
+/// Runs each of a day's examples from its puzzle text, one report per example.
+/// `overrides` from the command line replace the examples' own parameters.
+pub fn run_examples(
+    info: &SolutionInfo,
+    options: &RunOptions,
+    overrides: &RunContext,
+) -> Vec<DayReport> {
+    let blocks = doc_blocks(info.source);
+
+    (info.new)()
+        .examples()
+        .into_iter()
+        .map(|example| {
+            let input = load_input(info, || {
+                blocks.get(example.block).cloned().ok_or_else(|| {
+                    format!(
+                        "no ```text block {} in the docs for day {}",
+                        example.block, info.day
+                    )
+                })
+            });
+
+            run_parts(
+                info,
+                format!("example {}", example.block),
+                input,
+                options,
+                &RunContext::example()
+                    .with_params(&example.params)
+                    .overridden_by(overrides),
+                |_, part| example.expected(part),
+                |_| {},
+            )
+        })
+        .collect()
+}
+
+/// Runs a day on the input its generator makes from `seed`, or `None` if the day has no
+/// generator. There are no answers to check, so only errors and panics fail. The day's
+/// [`crate::Solution::generated_params`] apply, with `overrides` over them.
+pub fn run_generated(
+    info: &SolutionInfo,
+    seed: u64,
+    size: usize,
+    options: &RunOptions,
+    overrides: &RunContext,
+) -> Option<DayReport> {
+    let solution = (info.new)();
+    let input = solution.generate(seed, size)?;
+
+    Some(run_parts(
+        info,
+        format!("seed {}", seed),
+        load_input(info, || Ok(input)),
+        options,
+        &RunContext::real()
+            .with_params(&solution.generated_params(size))
+            .overridden_by(overrides),
+        |_, _| None,
+        |_| {},
+    ))
+}
+
+/// Every implementation of one part, run on the same input.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub part: u8,
+    /// Each implementation's name and result, the default one first
+    pub implementations: Vec<(&'static str, PartReport)>,
+}
+
+impl Comparison {
+    /// Whether every implementation succeeded with the same answer.
+    pub fn agrees(&self) -> bool {
+        let mut answers = self
+            .implementations
+            .iter()
+            .map(|(_, report)| report.answer.as_ref().ok());
+        match answers.next() {
+            Some(Some(first)) => answers.all(|answer| answer == Some(first)),
+            _ => false,
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        !self.agrees()
+            || self
+                .implementations
+                .iter()
+                .any(|(_, report)| report.is_failure())
+    }
+}
+
+/// Runs every implementation of each part on `input`, which is only parsed once.
+pub fn run_comparison(
+    info: &SolutionInfo,
+    input: &str,
+    options: &RunOptions,
+    overrides: &RunContext,
+    expected: impl Fn(&dyn DynSolution, u8) -> Option<Answer>,
+) -> Result<Vec<Comparison>, String> {
+    let mut first = (info.new)();
+    run_parse(first.as_mut(), input, options).0?;
+
+    let mut solution = Some(first);
+    let mut comparisons = vec![];
+
+    for part in options.parts() {
+        let current = solution.get_or_insert_with(|| fresh_solution(info, input));
+        let names = current.implementations(part);
+        let expected = expected(current.as_ref(), part);
+        let mut implementations = vec![];
+
+        for name in names {
+            solution.get_or_insert_with(|| fresh_solution(info, input));
+            let context = RunContext::real()
+                .overridden_by(overrides)
+                .with_implementation(name);
+            let (answer, time, alloc, _) = run_part(&mut solution, part, options, &context);
+
+            implementations.push((
+                name,
+                PartReport {
+                    part,
+                    answer,
+                    expected: expected.clone(),
+                    time,
+                    alloc,
+                },
+            ));
+        }
+
+        comparisons.push(Comparison {
+            part,
+            implementations,
+        });
+    }
+
+    Ok(comparisons)
+}
+
+/// Prints whether a part's implementations agree, then each one's answer and median
+/// time, and how much faster or slower than the default implementation it is.
+pub fn print_comparison(comparison: &Comparison) {
+    match comparison.implementations.len() {
+        1 => println!("Part {}: only the default implementation", comparison.part),
+        count => println!(
+            "Part {}: {} implementations {}",
+            comparison.part,
+            count,
+            if comparison.agrees() {
+                "agree".green()
+            } else {
+                "disagree".red()
+            }
+        ),
+    }
+
+    let baseline = comparison
+        .implementations
+        .first()
+        .map(|(_, report)| report.time.median)
+        .filter(|median| !median.is_zero());
+
+    for (i, (name, report)) in comparison.implementations.iter().enumerate() {
+        let answer = match &report.answer {
+            Ok(answer) => truncate(&answer.to_string(), 20),
+            Err(e) => truncate(e, 20),
+        };
+        let speedup = baseline.map(|baseline| {
+            baseline.as_secs_f64() / report.time.median.as_secs_f64().max(f64::MIN_POSITIVE)
+        });
+        let relative = match speedup {
+            _ if i == 0 => "baseline".to_string(),
+            None => "-".to_string(),
+            Some(speedup) if speedup >= 1.0 => format!("{:.2}x faster", speedup),
+            Some(speedup) => format!("{:.2}x slower", 1.0 / speedup),
+        };
+
+        println!(
+            "  {:<16} {:<20} {:>12} {:>15} {}",
+            name,
+            answer,
+            format!("{:.2?}", report.time.median),
+            relative,
+            report.status()
+        );
+    }
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    let first_line = s.lines().next().unwrap_or("");
+    if first_line.chars().count() > max || first_line.len() != s.len() {
+        format!("{}…", first_line.chars().take(max - 1).collect::<String>())
+    } else {
+        first_line.to_string()
+    }
+}
+
+/// Runs `run` on every day, handing each result to `report` in day order.
+///
+/// In parallel, days are spread over rayon's thread pool and each result is reported as
+/// soon as it and every day before it have finished.
+pub fn for_each_day<R: Send>(
+    infos: &[&'static SolutionInfo],
+    parallel: bool,
+    run: impl Fn(&SolutionInfo) -> R + Sync,
+    mut report: impl FnMut(R),
+) {
+    if !parallel {
+        for info in infos {
+            report(run(info));
+        }
+        return;
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let run = &run;
+
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            infos
+                .par_iter()
+                .enumerate()
+                .for_each_with(sender, |sender, (i, info)| {
+                    // Sending only fails if `report` panicked, which ends the run anyway
+                    let _ = sender.send((i, run(info)));
+                });
+        });
+
+        let mut finished = BTreeMap::new();
+        let mut next = 0;
+
+        for (i, result) in receiver {
+            finished.insert(i, result);
+
+            while let Some(result) = finished.remove(&next) {
+                report(result);
+                next += 1;
+            }
+        }
+    });
+}
+
+/// Prints one part's answer and whether it matched, with the expected answer if it didn't.
+pub fn print_part(part: &PartReport) {
+    match &part.answer {
+        Ok(answer) => match (part.status(), &part.expected) {
+            (status @ Status::Wrong, Some(expected)) => println!(
+                "Part {}: {} {} (expected {})",
+                part.part, answer, status, expected
+            ),
+            (status, _) => println!("Part {}: {} {}", part.part, answer, status),
+        },
+        Err(e) => println!("Part {} failed: {} {}", part.part, e, part.status()),
+    }
+}
+
+/// Prints the table header; with more than one run, each part gets min, mean and p95
+/// columns next to the median.
+pub fn print_summary_header(options: &RunOptions) {
+    print!(
+        "{:>3} {:<22} {:>4}  {:<20} {:<20} {:>12}",
+        "Day", "Title", "Part", "Answer", "Expected", "Time"
+    );
+
+    if options.runs > 1 {
+        print!(" {:>12} {:>12} {:>12}", "Min", "Mean", "P95");
+    }
+
+    if alloc_count::ENABLED {
+        print!(" {:>10} {:>12} {:>12}", "Allocs", "Allocated", "Peak");
+    }
+
+    println!();
+}
+
+pub fn print_summary_rows(report: &DayReport, options: &RunOptions) {
+    for part in &report.parts {
+        let answer = match &part.answer {
+            Ok(answer) => truncate(&answer.to_string(), 20),
+            Err(e) => truncate(e, 20),
+        };
+        print!(
+            "{:>3} {:<22} {:>4}  {:<20} {:<20} {:>12}",
+            report.day,
+            truncate(report.title, 22),
+            part.part,
+            answer,
+            truncate(
+                &part
+                    .expected
+                    .as_ref()
+                    .map_or("-".to_string(), Answer::to_string),
+                20
+            ),
+            format!("{:.2?}", part.time.median),
+        );
+
+        if options.runs > 1 {
+            print!(
+                " {:>12} {:>12} {:>12}",
+                format!("{:.2?}", part.time.min),
+                format!("{:.2?}", part.time.mean),
+                format!("{:.2?}", part.time.p95),
+            );
+        }
+
+        if let Some(alloc) = part.alloc {
+            print!(
+                " {:>10} {:>12} {:>12}",
+                alloc.allocations,
+                alloc_count::format_bytes(alloc.bytes),
+                alloc_count::format_bytes(alloc.peak),
+            );
+        }
+
+        println!(" {}", part.status());
+    }
+}
+
+/// Prints how long a whole day took, for runs where days overlap.
+pub fn print_run_time(report: &DayReport) {
+    println!(
+        "{:>3} {:<22} {:>4}  {}",
+        report.day,
+        truncate(report.title, 22),
+        "",
+        report.run_time.to_string().dimmed()
+    );
+}
+
+pub fn print_summary_total(reports: &[DayReport]) {
+    let failures = reports.iter().filter(|report| report.is_failure()).count();
+    let total = reports.iter().map(DayReport::elapsed).sum::<Duration>();
+
+    println!(
+        "{} days, {} failed, total {:.2?}",
+        reports.len(),
+        failures,
+        total
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(answer: Result<Answer, &str>, expected: Option<Answer>) -> PartReport {
+        PartReport {
+            part: 1,
+            answer: answer.map_err(String::from),
+            expected,
+            time: Stats::default(),
+            alloc: None,
+        }
+    }
+
+    #[test]
+    fn test_status() {
+        assert_eq!(
+            part(Ok(3.into()), Some(3u64.into())).status(),
+            Status::Correct
+        );
+        assert_eq!(part(Ok(3.into()), Some(4.into())).status(), Status::Wrong);
+        assert_eq!(part(Ok(3.into()), None).status(), Status::Unchecked);
+        assert_eq!(part(Ok(Answer::Unsolved), None).status(), Status::Unsolved);
+        assert_eq!(part(Err("boom"), Some(3.into())).status(), Status::Failed);
+        assert!(!part(Ok(3.into()), None).is_failure());
+        assert!(!part(Ok(Answer::Unsolved), Some(3.into())).is_failure());
+    }
+
+    #[test]
+    fn test_for_each_day_keeps_order() {
+        let infos = crate::list_solutions();
+
+        for parallel in [false, true] {
+            let mut days = vec![];
+            for_each_day(
+                &infos,
+                parallel,
+                |info| {
+                    // Make later days finish first
+                    let index = infos.iter().position(|other| std::ptr::eq(*other, info));
+                    let remaining = infos.len() - index.unwrap();
+                    std::thread::sleep(Duration::from_millis(remaining as u64));
+                    info.day
+                },
+                |day| days.push(day),
+            );
+
+            assert_eq!(days, infos.iter().map(|info| info.day).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+
+use crate::{input::input_dir, input_name};
+
+/// A new day's source, laid out like the existing days.
+fn day_template(year: u16, day: u8, title: &str) -> String {
+    format!(
+        r##"//! --- Day {day}: {title} ---
+//!
+//! Paste the puzzle text here, with the example input in a block like this:
+//!
+//! ```text
+//! ```
+
+use anyhow::Result;
+
+use crate::{{Answer, Example, RunContext, Solution}};
+
+pub struct Day{day} {{}}
+
+register_solution!(Day{day}, year = {year}, day = {day}, title = {title:?});
+
+impl Solution for Day{day} {{
+    fn new() -> Self {{
+        Self {{}}
+    }}
+
+    fn part1(&mut self, _input: &String, _context: &RunContext) -> Result<Answer> {{
+        Ok(Answer::Unsolved)
+    }}
+
+    fn part2(&mut self, _input: &String, _context: &RunContext) -> Result<Answer> {{
+        Ok(Answer::Unsolved)
+    }}
+
+    fn examples(&self) -> Vec<Example> {{
+        // Add the example answers with `.part1(..)` and `.part2(..)`
+        vec![Example::new(0)]
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_part1() {{
+        let mut solution = Day{day}::new();
+        assert_eq!(solution.solve(1, r#""#).unwrap(), Answer::Unsolved);
+    }}
+}}
+"##
+    )
+}
+
+/// Adds `pub mod {name};` to the `pub mod` lines of a module file, keeping them sorted.
+fn add_module(path: &Path, name: &str) -> Result<()> {
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    let line = format!("pub mod {};", name);
+
+    let mut lines = contents.lines().collect::<Vec<_>>();
+    if lines.contains(&line.as_str()) {
+        bail!("{:?} already declares {}", path, name);
+    }
+
+    let modules = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("pub mod "))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let at = modules
+        .iter()
+        .find(|&&i| lines[i] > line.as_str())
+        .copied()
+        .or_else(|| modules.last().map(|i| i + 1))
+        .unwrap_or(lines.len());
+    lines.insert(at, &line);
+
+    std::fs::write(path, lines.join("\n") + "\n")
+        .with_context(|| format!("Failed to write {:?}", path))
+}
+
+fn create_new(path: &Path, contents: &str) -> Result<()> {
+    std::fs::File::create_new(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .with_context(|| format!("Failed to create {:?}", path))
+}
+
+/// Creates `src/y{year}/day{day}.rs` and an empty input for it under `root`, and declares
+/// the new module (and the year's, if it's the first day of the year). Refuses to touch
+/// anything if the day's source or input already exists.
+///
+/// Returns the files created or changed.
+pub fn new_day(root: &Path, year: u16, day: u8, title: &str) -> Result<Vec<PathBuf>> {
+    let year_dir = root.join("src").join(format!("y{}", year));
+    let source = year_dir.join(format!("day{}.rs", day));
+    let input = root.join(input_dir(year)).join(input_name(day));
+
+    for path in [&source, &input] {
+        if path.exists() {
+            bail!("{:?} already exists", path);
+        }
+    }
+
+    let mut changed = vec![];
+    let year_mod = year_dir.join("mod.rs");
+
+    if !year_mod.exists() {
+        let lib = root.join("src").join("lib.rs");
+        std::fs::create_dir_all(&year_dir)
+            .with_context(|| format!("Failed to create {:?}", year_dir))?;
+        create_new(&year_mod, &format!("//! Advent of Code {}\n\n", year))?;
+        add_module(&lib, &format!("y{}", year))?;
+        changed.push(lib);
+    }
+
+    add_module(&year_mod, &format!("day{}", day))?;
+    changed.push(year_mod);
+
+    create_new(&source, &day_template(year, day, title))?;
+    changed.push(source);
+
+    let input_dir = input.parent().expect("inputs live in a directory");
+    std::fs::create_dir_all(input_dir)
+        .with_context(|| format!("Failed to create {:?}", input_dir))?;
+    create_new(&input, "")?;
+    changed.push(input);
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(
+            root.join("src/lib.rs"),
+            "pub mod answer;\npub mod y2025;\n\npub fn f() {}\n",
+        )
+        .unwrap();
+
+        new_day(&root, 2026, 2, "Second").unwrap();
+        new_day(&root, 2026, 10, r#"The "Tenth" \ Day"#).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+            "pub mod answer;\npub mod y2025;\npub mod y2026;\n\npub fn f() {}\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/y2026/mod.rs")).unwrap(),
+            "//! Advent of Code 2026\n\npub mod day10;\npub mod day2;\n"
+        );
+        assert!(
+            std::fs::read_to_string(root.join("src/y2026/day2.rs"))
+                .unwrap()
+                .contains("register_solution!(Day2, year = 2026, day = 2, title = \"Second\");")
+        );
+        assert!(
+            std::fs::read_to_string(root.join("src/y2026/day10.rs"))
+                .unwrap()
+                .contains(r#"title = "The \"Tenth\" \\ Day");"#)
+        );
+        assert!(root.join(input_dir(2026)).join("2_input.txt").exists());
+
+        let error = new_day(&root, 2026, 2, "Again").unwrap_err();
+        assert!(error.to_string().ends_with("day2.rs\" already exists"));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,324 @@
+use std::time::{Duration, Instant};
+
+/// Which parts to run, and how many times to run each timed step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunOptions {
+    /// Measured runs
+    pub runs: usize,
+    /// Unmeasured runs before the measured ones
+    pub warmup: usize,
+    /// Only run this part, rather than both
+    pub part: Option<u8>,
+    /// Give up on a part that takes longer than this, across all of its runs
+    pub timeout: Option<Duration>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            runs: 1,
+            warmup: 0,
+            part: None,
+            timeout: None,
+        }
+    }
+}
+
+impl RunOptions {
+    pub fn parts(&self) -> impl Iterator<Item = u8> {
+        let part = self.part;
+        [1, 2]
+            .into_iter()
+            .filter(move |&p| part.is_none_or(|part| part == p))
+    }
+}
+
+/// Parses a duration like `500ms`, `1.5s`, `2m` or `1h`. A bare number is in seconds.
+pub fn parse_duration(s: &str) -> anyhow::Result<Duration> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+
+    let number = number
+        .parse::<f64>()
+        .map_err(|_| anyhow::anyhow!("invalid duration {:?}", s))?;
+    let seconds = match unit.trim() {
+        "ms" => number / 1000.0,
+        "" | "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        unit => anyhow::bail!("unknown unit {:?} in {:?}, expected ms, s, m or h", unit, s),
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|_| anyhow::anyhow!("invalid duration {:?}", s))
+}
+
+/// Summary statistics over repeated timings of the same step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        samples.sort();
+
+        let runs = samples.len();
+        let median = if runs % 2 == 1 {
+            samples[runs / 2]
+        } else {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        };
+        // Nearest-rank percentile
+        let p95 = samples[(runs * 95).div_ceil(100) - 1];
+
+        Self {
+            runs,
+            min: samples[0],
+            median,
+            mean: samples.iter().sum::<Duration>() / runs as u32,
+            p95,
+        }
+    }
+}
+
+impl std::fmt::Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.runs <= 1 {
+            return write!(f, "{:.2?}", self.median);
+        }
+
+        write!(
+            f,
+            "min {:.2?}, median {:.2?}, mean {:.2?}, p95 {:.2?} over {} runs",
+            self.min, self.median, self.mean, self.p95, self.runs
+        )
+    }
+}
+
+/// Runs `f` `options.warmup + options.runs` times, timing only the measured runs.
+///
+/// Stops at the first error, returning it with the timings so far.
+pub fn measure<T, E>(
+    options: &RunOptions,
+    mut f: impl FnMut() -> Result<T, E>,
+) -> (Result<T, E>, Stats) {
+    for _ in 0..options.warmup {
+        if let Err(e) = f() {
+            return (Err(e), Stats::default());
+        }
+    }
+
+    let mut samples = Vec::with_capacity(options.runs);
+    let mut result = None;
+
+    for _ in 0..options.runs.max(1) {
+        let start = Instant::now();
+        let value = f();
+        samples.push(start.elapsed());
+
+        let failed = value.is_err();
+        result = Some(value);
+        if failed {
+            break;
+        }
+    }
+
+    (
+        result.expect("measure always runs at least once"),
+        Stats::from_samples(&mut samples),
+    )
+}
+
+/// Which CPU time clock a [`Stopwatch`] reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuClock {
+    /// Only the calling thread, so work a solution hands to other threads isn't counted
+    Thread,
+    /// Every thread in the process
+    Process,
+}
+
+/// CPU time used so far on `clock`, on platforms that can tell us.
+#[cfg(unix)]
+pub fn cpu_time(clock: CpuClock) -> Option<Duration> {
+    let clock = match clock {
+        CpuClock::Thread => libc::CLOCK_THREAD_CPUTIME_ID,
+        CpuClock::Process => libc::CLOCK_PROCESS_CPUTIME_ID,
+    };
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+
+    // SAFETY: `time` is a valid timespec for clock_gettime to write to
+    if unsafe { libc::clock_gettime(clock, &mut time) } != 0 {
+        return None;
+    }
+
+    Some(Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+}
+
+#[cfg(not(unix))]
+pub fn cpu_time(_clock: CpuClock) -> Option<Duration> {
+    None
+}
+
+/// Wall and CPU time spent on a stretch of work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Elapsed {
+    pub wall: Duration,
+    pub cpu: Option<Duration>,
+}
+
+impl Elapsed {
+    /// This with `cpu` more CPU time, spent on threads the stopwatch's clock didn't see.
+    pub fn with_cpu(self, cpu: Duration) -> Self {
+        Self {
+            cpu: self.cpu.map(|own| own + cpu),
+            ..self
+        }
+    }
+}
+
+impl std::fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "wall {:.2?}", self.wall)?;
+
+        match self.cpu {
+            Some(cpu) => write!(f, ", CPU {:.2?}", cpu),
+            None => write!(f, ", CPU unavailable"),
+        }
+    }
+}
+
+pub struct Stopwatch {
+    clock: CpuClock,
+    wall: Instant,
+    cpu: Option<Duration>,
+}
+
+impl Stopwatch {
+    pub fn start(clock: CpuClock) -> Self {
+        Self {
+            clock,
+            wall: Instant::now(),
+            cpu: cpu_time(clock),
+        }
+    }
+
+    pub fn elapsed(&self) -> Elapsed {
+        Elapsed {
+            wall: self.wall.elapsed(),
+            cpu: self
+                .cpu
+                .zip(cpu_time(self.clock))
+                .map(|(start, now)| now.saturating_sub(start)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(samples: &[u64]) -> Vec<Duration> {
+        samples
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&mut ms(&[5, 1, 4, 2, 3]));
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+
+        let stats = Stats::from_samples(&mut ms(&[4, 1, 2, 3]));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+
+        let mut samples = ms(&(1..=100).collect::<Vec<_>>());
+        assert_eq!(
+            Stats::from_samples(&mut samples).p95,
+            Duration::from_millis(95)
+        );
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+        let (result, stats) = measure(
+            &RunOptions {
+                runs: 3,
+                warmup: 2,
+                ..Default::default()
+            },
+            || {
+                calls += 1;
+                Ok::<_, ()>(calls)
+            },
+        );
+        assert_eq!(result, Ok(5));
+        assert_eq!(stats.runs, 3);
+
+        let (result, stats) = measure(
+            &RunOptions {
+                runs: 3,
+                ..Default::default()
+            },
+            || Err::<(), _>("no"),
+        );
+        assert_eq!(result, Err("no"));
+        assert_eq!(stats.runs, 1);
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("1.5s").unwrap(), Duration::from_millis(1500));
+        assert_eq!(parse_duration("2").unwrap(), Duration::from_secs(2));
+        assert_eq!(parse_duration("2m").unwrap(), Duration::from_secs(120));
+        assert!(parse_duration("2 weeks").is_err());
+        assert!(parse_duration("s").is_err());
+    }
+
+    #[test]
+    fn test_parts() {
+        let options = RunOptions::default();
+        assert_eq!(options.parts().collect::<Vec<_>>(), vec![1, 2]);
+
+        let options = RunOptions {
+            part: Some(2),
+            ..options
+        };
+        assert_eq!(options.parts().collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cpu_time() {
+        let stopwatch = Stopwatch::start(CpuClock::Thread);
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_millis(5) {
+            std::hint::spin_loop();
+        }
+
+        let elapsed = stopwatch.elapsed();
+        assert!(elapsed.cpu.unwrap() > Duration::ZERO);
+        assert!(elapsed.wall >= Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,77 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// How often `--watch` checks its files for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The input name `--watch` also runs a day on when `{day}_scratch.txt` exists, for
+/// trying out small inputs next to the real one.
+pub const SCRATCH_INPUT: &str = "scratch";
+
+/// What a file looked like when last polled, or `None` if it didn't exist.
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Notices changes to files by polling their modification time and size, so it works
+/// anywhere without platform file notification APIs.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    /// `None` until the first poll, so the first poll always counts as a change
+    seen: Option<Vec<Stamp>>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        Self { paths, seen: None }
+    }
+
+    /// Whether any file was changed, created or removed since the last call.
+    pub fn changed(&mut self) -> bool {
+        let stamps = self
+            .paths
+            .iter()
+            .map(|path| stamp(path))
+            .collect::<Vec<_>>();
+        let changed = self.seen.as_ref() != Some(&stamps);
+        self.seen = Some(stamps);
+        changed
+    }
+
+    /// Blocks until a file changes, polling every [`POLL_INTERVAL`].
+    pub fn wait(&mut self) {
+        while !self.changed() {
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut watcher = Watcher::new(vec![path.clone()]);
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        std::fs::write(&path, "1").unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        std::fs::write(&path, "12").unwrap();
+        assert!(watcher.changed());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(watcher.changed());
+    }
+}
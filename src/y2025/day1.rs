@@ -60,65 +60,90 @@
 //!
 //! Analyze the rotations in your attached document. What's the actual password to open the door?
 
-use crate::Solution;
+use anyhow::{Context, Result, bail};
+
+use rand::Rng;
+
+use crate::{Answer, Example, RunContext, Solution, generate, parse_lines};
 
 pub struct Day1 {}
 
+register_solution!(Day1, year = 2025, day = 1, title = "Secret Entrance");
+
+/// Parses a rotation like `L68` into a signed distance, with `L` positive.
+fn parse_rotation(line: &str) -> Result<i16> {
+    let line = line.trim();
+    let direction = line.chars().next().context("empty rotation")?;
+    let distance = line[direction.len_utf8()..]
+        .parse::<i16>()
+        .context("invalid distance")?;
+
+    match direction {
+        'L' => Ok(distance),
+        'R' => Ok(-distance),
+        _ => bail!("invalid direction {:?}", direction),
+    }
+}
+
 impl Solution for Day1 {
     fn new() -> Self {
         Self {}
     }
 
-    fn part1(&mut self, input: &str) -> String {
+    fn part1(&mut self, input: &String, _context: &RunContext) -> Result<Answer> {
         let mut dial_location = 50i16;
         let mut count_of_zeroes = 0;
 
-        for line in input.trim().lines().map(|l| l.trim()) {
-            let direction = line.chars().next().unwrap();
-            let distance = line[1..].parse::<i16>().unwrap();
-            match direction {
-                'L' => dial_location = (dial_location + distance) % 100,
-                'R' => dial_location = (dial_location - distance) % 100,
-                _ => unreachable!(),
-            }
+        for rotation in parse_lines(input.trim(), parse_rotation)? {
+            dial_location = (dial_location + rotation) % 100;
             if dial_location == 0 {
                 count_of_zeroes += 1;
             }
         }
 
-        count_of_zeroes.to_string()
+        Ok(count_of_zeroes.into())
     }
 
-    fn known_solution_part1(&self) -> Option<String> {
-        992.to_string().into()
+    fn known_solution_part1(&self) -> Option<Answer> {
+        Some(992.into())
     }
 
-    fn part2(&mut self, input: &str) -> String {
+    fn part2(&mut self, input: &String, _context: &RunContext) -> Result<Answer> {
         let mut dial_location = 50i16;
         let mut count_of_zeroes = 0;
 
-        for line in input.trim().lines().map(|l| l.trim()) {
-            let direction = line.chars().next().unwrap();
-            let distance = line[1..].parse::<i16>().unwrap();
-
+        for rotation in parse_lines(input.trim(), parse_rotation)? {
             let dial_sign = dial_location.signum();
 
-            match direction {
-                'L' => dial_location = dial_location + distance,
-                'R' => dial_location = dial_location - distance,
-                _ => unreachable!(),
-            }
+            dial_location += rotation;
 
             let new_dial_sign = dial_location.signum();
             count_of_zeroes += (dial_location / 100).abs();
-            dial_location = dial_location % 100;
+            dial_location %= 100;
 
             if (dial_sign != 0 && new_dial_sign != dial_sign) || new_dial_sign == 0 {
                 count_of_zeroes += 1;
             }
         }
 
-        count_of_zeroes.to_string()
+        Ok(count_of_zeroes.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(0).part1(3).part2(6)]
+    }
+
+    /// `size` rotations of up to `10 * size` clicks, so the default size is like the real
+    /// input and bigger sizes try longer rotations.
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        let mut rng = generate::rng(seed);
+        let rotations = (0..size)
+            .map(|_| {
+                let direction = if rng.gen_bool(0.5) { 'L' } else { 'R' };
+                format!("{}{}", direction, rng.gen_range(1..=10 * size.max(1)))
+            })
+            .collect::<Vec<_>>();
+        Some(rotations.join("\n"))
     }
 }
 
@@ -130,8 +155,10 @@ mod tests {
     fn test_part1() {
         let mut solution = Day1::new();
         assert_eq!(
-            solution.part1(
-                r#"L68
+            solution
+                .solve(
+                    1,
+                    r#"L68
         L30
         R48
         L5
@@ -141,8 +168,9 @@ mod tests {
         L99
         R14
         L82"#
-            ),
-            String::from("3")
+                )
+                .unwrap(),
+            Answer::from(3)
         );
     }
 
@@ -150,8 +178,10 @@ mod tests {
     fn test_part2() {
         let mut solution = Day1::new();
         assert_eq!(
-            solution.part2(
-                r#"L68
+            solution
+                .solve(
+                    2,
+                    r#"L68
         L30
         R48
         L5
@@ -161,8 +191,19 @@ mod tests {
         L99
         R14
         L82"#
-            ),
-            String::from("6")
+                )
+                .unwrap(),
+            Answer::from(6)
+        );
+    }
+
+    #[test]
+    fn test_invalid_direction() {
+        let mut solution = Day1::new();
+        let error = solution.solve(1, "L68\nX30").unwrap_err();
+        assert_eq!(
+            format!("{:#}", error),
+            r#"line 2: "X30": invalid direction 'X'"#
         );
     }
 }
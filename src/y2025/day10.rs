@@ -58,23 +58,22 @@ use crate::Solution;
 
 pub struct Day10 {}
 
+register_solution!(Day10, year = 2025, day = 10, title = "Factory");
+
 impl Solution for Day10 {
     fn new() -> Self {
         Self {}
     }
-
-    fn part1(&mut self, input: &str) -> String {
-        input.to_string()
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Answer;
 
     #[test]
     fn test_part1() {
         let mut solution = Day10::new();
-        assert_eq!(solution.part1(r#""#), String::from(""));
+        assert_eq!(solution.solve(1, r#""#).unwrap(), Answer::Unsolved);
     }
 }
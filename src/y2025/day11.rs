@@ -39,27 +39,65 @@
 //!
 //! How many different paths lead from you to out?
 
-use crate::Solution;
+use std::collections::HashSet;
+
+use rand::{Rng, seq::SliceRandom};
+
+use crate::{Solution, generate};
 
 pub struct Day11 {}
 
+register_solution!(Day11, year = 2025, day = 11, title = "Reactor");
+
 impl Solution for Day11 {
     fn new() -> Self {
         Self {}
     }
 
-    fn part1(&mut self, input: &str) -> String {
-        input.to_string()
+    /// `size` devices wired into a DAG, each with up to 3 outputs to devices a little
+    /// further along, with `you` near the start and everything leading to `out`.
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        let mut rng = generate::rng(seed);
+        let count = size.max(2);
+
+        let mut names = HashSet::from(["you".to_string(), "out".to_string()]);
+        let mut devices = vec![];
+        while devices.len() < count - 2 {
+            let name = (0..3)
+                .map(|_| rng.gen_range(b'a'..=b'z') as char)
+                .collect::<String>();
+            if names.insert(name.clone()) {
+                devices.push(name);
+            }
+        }
+        devices.insert(rng.gen_range(0..=devices.len() / 4), "you".to_string());
+        devices.push("out".to_string());
+
+        let mut lines = (0..devices.len() - 1)
+            .map(|i| {
+                let later = (i + 1..devices.len().min(i + 6)).collect::<Vec<_>>();
+                let amount = rng.gen_range(1..=3);
+                let outputs = later
+                    .choose_multiple(&mut rng, amount)
+                    .map(|&j| devices[j].as_str())
+                    .collect::<Vec<_>>();
+                format!("{}: {}", devices[i], outputs.join(" "))
+            })
+            .collect::<Vec<_>>();
+        lines.shuffle(&mut rng);
+
+        Some(lines.join("\n"))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Answer;
 
     #[test]
     fn test_part1() {
         let mut solution = Day11::new();
-        assert_eq!(solution.part1(r#""#), String::from(""));
+        assert_eq!(solution.solve(1, r#""#).unwrap(), Answer::Unsolved);
     }
 }
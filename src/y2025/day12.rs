@@ -95,23 +95,22 @@ use crate::Solution;
 
 pub struct Day12 {}
 
+register_solution!(Day12, year = 2025, day = 12, title = "Christmas Tree Farm");
+
 impl Solution for Day12 {
     fn new() -> Self {
         Self {}
     }
-
-    fn part1(&mut self, input: &str) -> String {
-        input.to_string()
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Answer;
 
     #[test]
     fn test_part1() {
         let mut solution = Day12::new();
-        assert_eq!(solution.part1(r#""#), String::from(""));
+        assert_eq!(solution.solve(1, r#""#).unwrap(), Answer::Unsolved);
     }
 }
@@ -36,22 +36,42 @@
 //!
 //! What do you get if you add up all of the invalid IDs?
 
-use crate::Solution;
+use anyhow::{Context, Result};
+
+use rand::Rng;
+
+use crate::{Answer, Example, RunContext, Solution, generate};
 
 pub struct Day2 {}
 
+register_solution!(Day2, year = 2025, day = 2, title = "Gift Shop");
+
+fn parse_range(range: &str) -> Result<(u64, u64)> {
+    // The puzzle text wraps the example ranges across lines
+    let (lower, upper) = range.trim().split_once('-').context("missing '-'")?;
+    Ok((
+        lower.parse().context("invalid lower bound")?,
+        upper.parse().context("invalid upper bound")?,
+    ))
+}
+
+fn parse_ranges(input: &str) -> Result<Vec<(u64, u64)>> {
+    input
+        .trim()
+        .split(",")
+        .map(|range| parse_range(range).with_context(|| format!("range {:?}", range)))
+        .collect()
+}
+
 impl Solution for Day2 {
     fn new() -> Self {
         Self {}
     }
 
-    fn part1(&mut self, input: &str) -> String {
+    fn part1(&mut self, input: &String, _context: &RunContext) -> Result<Answer> {
         let mut total: u64 = 0;
 
-        for range in input.trim().split(",") {
-            let (lower, upper) = range.split_once('-').unwrap();
-            let lower: u64 = lower.parse().unwrap();
-            let upper: u64 = upper.parse().unwrap();
+        for (lower, upper) in parse_ranges(input)? {
             if lower.ilog10() % 2 == 0 && upper.ilog10() % 2 == 0 {
                 continue;
             }
@@ -64,17 +84,13 @@ impl Solution for Day2 {
             }
         }
 
-        total.to_string()
+        Ok(total.into())
     }
 
-    fn part2(&mut self, input: &str) -> String {
+    fn part2(&mut self, input: &String, _context: &RunContext) -> Result<Answer> {
         let mut total: u64 = 0;
 
-        for range in input.trim().split(",") {
-            let (lower, upper) = range.split_once('-').unwrap();
-            let lower: u64 = lower.parse().unwrap();
-            let upper: u64 = upper.parse().unwrap();
-
+        for (lower, upper) in parse_ranges(input)? {
             'next_number: for x in lower..=upper {
                 let xstr = x.to_string();
                 'digit_count: for i in 1..=xstr.len() / 2 {
@@ -94,7 +110,24 @@ impl Solution for Day2 {
             }
         }
 
-        total.to_string()
+        Ok(total.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(0).part1(1227775554).part2(4174379265u64)]
+    }
+
+    /// `size` ranges of up to 1000 IDs, starting anywhere from 1 to 12 digits.
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        let mut rng = generate::rng(seed);
+        let ranges = (0..size)
+            .map(|_| {
+                let digits = rng.gen_range(1..=12);
+                let lower = rng.gen_range(10u64.pow(digits - 1)..10u64.pow(digits));
+                format!("{}-{}", lower, lower + rng.gen_range(0..1000))
+            })
+            .collect::<Vec<_>>();
+        Some(ranges.join(","))
     }
 }
 
@@ -106,10 +139,12 @@ mod tests {
     fn test_part1() {
         let mut solution = Day2::new();
         assert_eq!(
-            solution.part1(
+            solution.solve(
+                1,
                 r#"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124"#
-            ),
-            String::from("1227775554")
+            )
+            .unwrap(),
+            Answer::from(1227775554)
         );
     }
 
@@ -117,10 +152,12 @@ mod tests {
     fn test_part2() {
         let mut solution = Day2::new();
         assert_eq!(
-            solution.part2(
+            solution.solve(
+                2,
                 r#"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124"#
-            ),
-            String::from("4174379265")
+            )
+            .unwrap(),
+            Answer::from(4174379265u64)
         );
     }
 }
@@ -55,10 +55,14 @@
 //!
 //! What is the new total output joltage?
 
-use crate::Solution;
+use anyhow::{Context, Result, ensure};
+
+use crate::{Answer, Example, RunContext, Solution, parse_lines};
 
 pub struct Day3 {}
 
+register_solution!(Day3, year = 2025, day = 3, title = "Lobby");
+
 fn recur_count_solve(rem: &[u8], digits_left: u8) -> i64 {
     if digits_left == 0 {
         return 0;
@@ -78,45 +82,58 @@ fn recur_count_solve(rem: &[u8], digits_left: u8) -> i64 {
         + recur_count_solve(&rem[best_index + 1..], digits_left - 1)
 }
 
+/// Parses a bank of batteries, making sure it has enough of them to pick `digits` from.
+fn parse_bank(line: &str, digits: u8) -> Result<Vec<u8>> {
+    let bank = line
+        .chars()
+        .map(|c| {
+            c.to_digit(10)
+                .map(|d| d as u8)
+                .with_context(|| format!("invalid battery {:?}", c))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    ensure!(
+        bank.len() >= digits as usize,
+        "need at least {} batteries, found {}",
+        digits,
+        bank.len()
+    );
+
+    Ok(bank)
+}
+
 impl Solution for Day3 {
     fn new() -> Self {
         Self {}
     }
 
-    fn part1(&mut self, input: &str) -> String {
-        input
-            .trim()
-            .lines()
-            .map(|line| {
-                recur_count_solve(
-                    &line.as_bytes().iter().map(|b| b - b'0').collect::<Vec<_>>(),
-                    2,
-                )
-            })
+    fn part1(&mut self, input: &String, _context: &RunContext) -> Result<Answer> {
+        Ok(parse_lines(input.trim(), |line| parse_bank(line, 2))?
+            .iter()
+            .map(|bank| recur_count_solve(bank, 2))
             .sum::<i64>()
-            .to_string()
+            .into())
     }
 
-    fn known_solution_part1(&self) -> Option<String> {
-        Some(String::from("17493"))
+    fn known_solution_part1(&self) -> Option<Answer> {
+        Some(Answer::from(17493))
     }
 
-    fn part2(&mut self, input: &str) -> String {
-        input
-            .trim()
-            .lines()
-            .map(|line| {
-                recur_count_solve(
-                    &line.as_bytes().iter().map(|b| b - b'0').collect::<Vec<_>>(),
-                    12,
-                )
-            })
+    fn part2(&mut self, input: &String, _context: &RunContext) -> Result<Answer> {
+        Ok(parse_lines(input.trim(), |line| parse_bank(line, 12))?
+            .iter()
+            .map(|bank| recur_count_solve(bank, 12))
             .sum::<i64>()
-            .to_string()
+            .into())
     }
 
-    fn known_solution_part2(&self) -> Option<String> {
-        Some(String::from("173685428989126"))
+    fn known_solution_part2(&self) -> Option<Answer> {
+        Some(Answer::from(173685428989126i64))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(0).part1(357).part2(3121910778619i64)]
     }
 }
 
@@ -128,13 +145,16 @@ mod tests {
     fn test_part1() {
         let mut solution = Day3::new();
         assert_eq!(
-            solution.part1(
-                r#"987654321111111
+            solution
+                .solve(
+                    1,
+                    r#"987654321111111
 811111111111119
 234234234234278
 818181911112111"#
-            ),
-            String::from("357")
+                )
+                .unwrap(),
+            Answer::from(357)
         );
     }
 }
@@ -44,12 +44,13 @@
 
 use std::fmt::Display;
 
+use anyhow::Result;
 use itertools::Itertools;
 
-use crate::{Solution, grid::Grid};
+use crate::{Answer, Example, FromInput, RunContext, Solution, grid::Grid};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Type {
+pub enum Type {
     Dot,
     Roll,
 }
@@ -63,25 +64,34 @@ impl Display for Type {
     }
 }
 
+impl FromInput for Grid<Type> {
+    fn from_input(input: &str) -> Result<Self> {
+        Grid::try_new(
+            input,
+            |c| match c {
+                '.' => Some(Type::Dot),
+                '@' => Some(Type::Roll),
+                _ => None,
+            },
+            false,
+        )
+    }
+}
+
 pub struct Day4 {}
 
+register_solution!(Day4, year = 2025, day = 4, title = "Printing Department");
+
 impl Solution for Day4 {
+    type Parsed = Grid<Type>;
+
     fn new() -> Self {
         Self {}
     }
 
-    fn part1(&mut self, input: &str) -> String {
-        let grid = Grid::new(
-            input,
-            |c| match c {
-                '.' => Type::Dot,
-                '@' => Type::Roll,
-                _ => panic!("Invalid character"),
-            },
-            false,
-        );
-
-        grid.iter_with_coords()
+    fn part1(&mut self, grid: &Grid<Type>, _context: &RunContext) -> Result<Answer> {
+        Ok(grid
+            .iter_with_coords()
             .filter(|(v, _)| **v == Type::Roll)
             .map(|(_, c)| (c, grid.survey_octo(c)))
             .filter(|(_, survey)| {
@@ -93,19 +103,11 @@ impl Solution for Day4 {
                     < 4
             })
             .count()
-            .to_string()
+            .into())
     }
 
-    fn part2(&mut self, input: &str) -> String {
-        let mut grid = Grid::new(
-            input,
-            |c| match c {
-                '.' => Type::Dot,
-                '@' => Type::Roll,
-                _ => panic!("Invalid character"),
-            },
-            false,
-        );
+    fn part2(&mut self, input: &Grid<Type>, _context: &RunContext) -> Result<Answer> {
+        let mut grid = input.clone();
 
         let mut count = 0;
 
@@ -136,7 +138,11 @@ impl Solution for Day4 {
             count += removable.len();
         }
 
-        count.to_string()
+        Ok(count.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(0).part1(13).part2(43)]
     }
 }
 
@@ -148,8 +154,10 @@ mod tests {
     fn test_part1() {
         let mut solution = Day4::new();
         assert_eq!(
-            solution.part1(
-                r#"..@@.@@@@.
+            solution
+                .solve(
+                    1,
+                    r#"..@@.@@@@.
 @@@.@.@.@@
 @@@@@.@.@@
 @.@@@@..@.
@@ -159,8 +167,9 @@ mod tests {
 @.@@@.@@@@
 .@@@@@@@@.
 @.@.@@@.@."#
-            ),
-            String::from("13")
+                )
+                .unwrap(),
+            Answer::from(13)
         );
     }
 
@@ -168,8 +177,10 @@ mod tests {
     fn test_part2() {
         let mut solution = Day4::new();
         assert_eq!(
-            solution.part2(
-                r#"..@@.@@@@.
+            solution
+                .solve(
+                    2,
+                    r#"..@@.@@@@.
 @@@.@.@.@@
 @@@@@.@.@@
 @.@@@@..@.
@@ -179,8 +190,9 @@ mod tests {
 @.@@@.@@@@
 .@@@@@@@@.
 @.@.@@@.@."#
-            ),
-            String::from("43")
+                )
+                .unwrap(),
+            Answer::from(43)
         );
     }
 }
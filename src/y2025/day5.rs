@@ -38,66 +38,91 @@
 //!
 //! Process the database file from the new inventory management system. How many of the available ingredient IDs are fresh?
 
-use crate::Solution;
+use anyhow::{Context, Result};
+
+use crate::{Answer, Example, FromInput, RunContext, Solution};
 
 pub struct Day5 {}
 
-impl Solution for Day5 {
-    fn new() -> Self {
-        Self {}
-    }
+register_solution!(Day5, year = 2025, day = 5, title = "Cafeteria");
 
-    fn part1(&mut self, input: &str) -> String {
-        let mut ranges = vec![];
+fn parse_range(line: &str) -> Result<(u64, u64)> {
+    let (lower, upper) = line.split_once('-').context("missing '-'")?;
+    Ok((
+        lower.parse().context("invalid lower bound")?,
+        upper.parse().context("invalid upper bound")?,
+    ))
+}
 
-        let mut lines = input.lines();
+pub struct Database {
+    ranges: Vec<(u64, u64)>,
+    ids: Vec<u64>,
+}
 
-        for line in lines.by_ref() {
-            if line.is_empty() {
-                break;
+/// Parses the fresh ID ranges and the available IDs, which are separated by a blank line.
+impl FromInput for Database {
+    fn from_input(input: &str) -> Result<Self> {
+        let mut ranges = vec![];
+        let mut ids = vec![];
+        let mut in_ranges = true;
+
+        for (i, line) in input.lines().enumerate() {
+            if in_ranges && line.is_empty() {
+                in_ranges = false;
+                continue;
             }
-            let (lower, upper) = line.split_once('-').unwrap();
-            let lower: u64 = lower.parse().unwrap();
-            let upper: u64 = upper.parse().unwrap();
-            ranges.push(lower..=upper);
+
+            let parsed = if in_ranges {
+                parse_range(line).map(|range| ranges.push(range))
+            } else {
+                line.parse().map(|id| ids.push(id)).context("invalid ID")
+            };
+
+            parsed.with_context(|| format!("line {}: {:?}", i + 1, line))?;
         }
 
+        Ok(Database { ranges, ids })
+    }
+}
+
+impl Solution for Day5 {
+    type Parsed = Database;
+
+    fn new() -> Self {
+        Self {}
+    }
+
+    fn part1(&mut self, input: &Database, _context: &RunContext) -> Result<Answer> {
+        let Database { ranges, ids } = input;
+
         let mut total = 0;
 
-        for line in lines.by_ref() {
-            let id: u64 = line.parse().unwrap();
-            if ranges.iter().any(|range| range.contains(&id)) {
+        for &id in ids {
+            if ranges
+                .iter()
+                .any(|&(lower, upper)| (lower..=upper).contains(&id))
+            {
                 total += 1;
             }
         }
 
-        total.to_string()
+        Ok(total.into())
     }
 
-    fn part2(&mut self, input: &str) -> String {
-        let mut ranges = vec![];
-
-        for line in input.lines() {
-            if line.is_empty() {
-                break;
-            }
-            let (lower, upper) = line.split_once('-').unwrap();
-            let lower: u64 = lower.parse().unwrap();
-            let upper: u64 = upper.parse().unwrap();
-            ranges.push((lower, upper));
-        }
+    fn part2(&mut self, input: &Database, _context: &RunContext) -> Result<Answer> {
+        let mut ranges = input.ranges.clone();
 
         // Go through each range and remove overlapping ranges
         ranges.sort_by_key(|range| range.0);
         let mut merged_ranges = vec![];
-        let mut current_range = ranges[0].clone();
+        let mut current_range = *ranges.first().context("no fresh ID ranges")?;
 
         for range in ranges.iter().skip(1) {
             if range.0 <= current_range.1 + 1 {
                 current_range.1 = current_range.1.max(range.1);
             } else {
                 merged_ranges.push(current_range);
-                current_range = range.clone();
+                current_range = *range;
             }
         }
 
@@ -108,7 +133,11 @@ impl Solution for Day5 {
             total += range.1 - range.0 + 1;
         }
 
-        total.to_string()
+        Ok(total.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(0).part1(3).part2(14)]
     }
 }
 
@@ -120,8 +149,10 @@ mod tests {
     fn test_part1() {
         let mut solution = Day5::new();
         assert_eq!(
-            solution.part1(
-                r#"3-5
+            solution
+                .solve(
+                    1,
+                    r#"3-5
 10-14
 16-20
 12-18
@@ -132,8 +163,9 @@ mod tests {
 11
 17
 32"#
-            ),
-            String::from("3")
+                )
+                .unwrap(),
+            Answer::from(3)
         );
     }
 
@@ -141,8 +173,10 @@ mod tests {
     fn test_part2() {
         let mut solution = Day5::new();
         assert_eq!(
-            solution.part2(
-                r#"3-5
+            solution
+                .solve(
+                    2,
+                    r#"3-5
 10-14
 16-20
 12-18
@@ -153,8 +187,9 @@ mod tests {
 11
 17
 32"#
-            ),
-            String::from("14")
+                )
+                .unwrap(),
+            Answer::from(14)
         );
     }
 }
@@ -12,8 +12,8 @@
 //!
 //! ```text
 //! 123 328  51 64
-//! 45 64  387 23
-//! 6 98  215 314
+//!  45 64  387 23
+//!   6 98  215 314
 //! *   +   *   +
 //! ```
 //! Each problem's numbers are arranged vertically; at the bottom of the problem is the symbol for the operation that needs to be performed. Problems are separated by a full column of only spaces. The left/right alignment of numbers within each problem can be ignored.
@@ -31,26 +31,44 @@
 //!
 //! Solve the problems on the math worksheet. What is the grand total found by adding together all of the answers to the individual problems?
 
-use crate::{Solution, grid::Grid};
+use anyhow::{Context, Result, bail, ensure};
+
+use crate::{
+    Answer, Example, RunContext, Solution,
+    grid::Grid,
+    input::{Normalize, Trim},
+};
 
 pub struct Day6 {}
 
+register_solution!(Day6, year = 2025, day = 6, title = "Trash Compactor");
+
 impl Solution for Day6 {
+    // Part 2 reads the numbers by column, so the first row's leading spaces matter. The
+    // rows' trailing spaces are dropped, as editors tend to, and padded back out
+    const NORMALIZE: Normalize = Normalize {
+        crlf: true,
+        trailing_spaces: true,
+        trim: Trim::TrailingNewline,
+    };
+
     fn new() -> Self {
         Self {}
     }
 
-    fn part1(&mut self, input: &str) -> String {
+    fn part1(&mut self, input: &String, _context: &RunContext) -> Result<Answer> {
         let mut vv = vec![];
         let mut ops = vec![];
 
-        for line in input.lines() {
+        for (i, line) in input.lines().enumerate() {
             let mut v = vec![];
             for num in line.split_whitespace() {
                 if let Ok(num) = num.parse::<i64>() {
                     v.push(num);
-                } else {
+                } else if num == "*" || num == "+" {
                     ops.push(num);
+                } else {
+                    bail!("line {}: unexpected token {:?}", i + 1, num);
                 }
             }
 
@@ -61,23 +79,26 @@ impl Solution for Day6 {
 
         let mut total = 0;
         for (i, op) in ops.iter().enumerate() {
-            total += vv.iter().map(|v| v.iter().nth(i)).fold(
+            total += vv.iter().map(|v| v.get(i)).try_fold(
                 if *op == "*" { 1 } else { 0 },
                 |acc, x| {
-                    if *op == "+" {
-                        acc + x.unwrap()
-                    } else {
-                        acc * x.unwrap()
-                    }
+                    let x = x.with_context(|| format!("problem {} is missing a number", i + 1))?;
+                    anyhow::Ok(if *op == "+" { acc + x } else { acc * x })
                 },
-            );
+            )?;
         }
 
-        total.to_string()
+        Ok(total.into())
     }
 
-    fn part2(&mut self, input: &str) -> String {
-        let grid = Grid::new(input, |c| c, false);
+    fn part2(&mut self, input: &String, _context: &RunContext) -> Result<Answer> {
+        let width = input.lines().map(str::len).max().unwrap_or(0);
+        let padded = input
+            .lines()
+            .map(|line| format!("{:<width$}", line))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let grid = Grid::try_new(&padded, Some, false)?;
 
         let mut vv = vec![];
         let mut ops = vec![];
@@ -100,13 +121,21 @@ impl Solution for Day6 {
                 }
                 v = vec![];
             } else {
-                v.push(s.parse().unwrap());
+                v.push(
+                    s.parse()
+                        .with_context(|| format!("invalid number {:?}", s))?,
+                );
             }
         }
 
         vv.push(v);
 
-        assert_eq!(vv.len(), ops.len());
+        ensure!(
+            vv.len() == ops.len(),
+            "found {} problems but {} operators",
+            vv.len(),
+            ops.len()
+        );
 
         let mut total = 0;
         for (op, v) in ops.iter().zip(vv.iter()) {
@@ -115,7 +144,11 @@ impl Solution for Day6 {
             });
         }
 
-        total.to_string()
+        Ok(total.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(0).part1(4277556).part2(3263827)]
     }
 }
 
@@ -128,17 +161,20 @@ mod tests {
     fn test_part1() {
         let mut solution = Day6::new();
         assert_eq!(
-            solution.part1(
-                &[
-                    "123 328  51 64 ",
-                    " 45 64  387 23 ",
-                    "  6 98  215 314",
-                    "*   +   *   +  "
-                ]
-                .iter()
-                .join("\n")
-            ),
-            String::from("4277556")
+            solution
+                .solve(
+                    1,
+                    &[
+                        "123 328  51 64 ",
+                        " 45 64  387 23 ",
+                        "  6 98  215 314",
+                        "*   +   *   +  "
+                    ]
+                    .iter()
+                    .join("\n")
+                )
+                .unwrap(),
+            Answer::from(4277556)
         );
     }
 
@@ -146,17 +182,20 @@ mod tests {
     fn test_part2() {
         let mut solution = Day6::new();
         assert_eq!(
-            solution.part2(
-                &[
-                    "123 328  51 64 ",
-                    " 45 64  387 23 ",
-                    "  6 98  215 314",
-                    "*   +   *   +  "
-                ]
-                .iter()
-                .join("\n")
-            ),
-            String::from("3263827")
+            solution
+                .solve(
+                    2,
+                    &[
+                        "123 328  51 64 ",
+                        " 45 64  387 23 ",
+                        "  6 98  215 314",
+                        "*   +   *   +  "
+                    ]
+                    .iter()
+                    .join("\n")
+                )
+                .unwrap(),
+            Answer::from(3263827)
         );
     }
 }
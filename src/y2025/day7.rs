@@ -136,14 +136,16 @@
 
 use std::collections::HashMap;
 
+use anyhow::{Context, Result};
+
 use crate::{
-    Solution,
+    Answer, Example, FromInput, Implementation, RunContext, Solution,
     direction::Direction,
     grid::{Coord, Grid},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Cell {
+pub enum Cell {
     Empty,
     Start,
     Splitter,
@@ -161,29 +163,77 @@ impl std::fmt::Display for Cell {
     }
 }
 
+impl FromInput for Grid<Cell> {
+    fn from_input(input: &str) -> Result<Self> {
+        Grid::try_new(
+            input,
+            |c| match c {
+                '.' => Some(Cell::Empty),
+                'S' => Some(Cell::Start),
+                '^' => Some(Cell::Splitter),
+                _ => None,
+            },
+            false,
+        )
+    }
+}
+
+/// Follows the beams down one row at a time, counting the timelines in each column,
+/// and returns how many splitters were hit and how many timelines came out the bottom.
+fn sweep(grid: &Grid<Cell>) -> Result<(u64, u64)> {
+    let start = grid
+        .find(Cell::Start)
+        .context("no start 'S' in the manifold")?;
+
+    let mut timelines = vec![0u64; grid.width() as usize];
+    timelines[start.col() as usize] = 1;
+    let mut splits = 0;
+
+    for row in start.row() + 1..grid.height() {
+        let mut next = vec![0; timelines.len()];
+
+        for (col, &count) in timelines
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+        {
+            if grid.get(Coord::new_usize(row as usize, col)) == Some(&Cell::Splitter) {
+                splits += 1;
+                // Beams split off the edge of the manifold are lost
+                for side in [col.checked_sub(1), Some(col + 1)].into_iter().flatten() {
+                    if let Some(next) = next.get_mut(side) {
+                        *next += count;
+                    }
+                }
+            } else {
+                next[col] += count;
+            }
+        }
+
+        timelines = next;
+    }
+
+    Ok((splits, timelines.iter().sum()))
+}
+
 pub struct Day7 {}
 
+register_solution!(Day7, year = 2025, day = 7, title = "Laboratories");
+
 impl Solution for Day7 {
+    type Parsed = Grid<Cell>;
+
     fn new() -> Self {
         Self {}
     }
 
-    fn part1(&mut self, input: &str) -> String {
-        let mut grid = Grid::new(
-            input,
-            |c| match c {
-                '.' => Cell::Empty,
-                'S' => Cell::Start,
-                '^' => Cell::Splitter,
-                _ => panic!("Invalid character"),
-            },
-            false,
-        );
+    fn part1(&mut self, input: &Grid<Cell>, _context: &RunContext) -> Result<Answer> {
+        let mut grid = input.clone();
 
-        grid.set(
-            grid.find(Cell::Start).unwrap() + Direction::Down,
-            Cell::Laser,
-        );
+        let start = grid
+            .find(Cell::Start)
+            .context("no start 'S' in the manifold")?;
+        grid.set(start + Direction::Down, Cell::Laser);
 
         loop {
             let cells_to_add_lasers = grid.find_where(|v, c| {
@@ -204,25 +254,15 @@ impl Solution for Day7 {
             }
         }
 
-        grid.find_where(|v, c| {
-            *v == Cell::Splitter && grid.get(c + Direction::Up) == Some(&Cell::Laser)
-        })
-        .len()
-        .to_string()
+        Ok(grid
+            .find_where(|v, c| {
+                *v == Cell::Splitter && grid.get(c + Direction::Up) == Some(&Cell::Laser)
+            })
+            .len()
+            .into())
     }
 
-    fn part2(&mut self, input: &str) -> String {
-        let grid = Grid::new(
-            input,
-            |c| match c {
-                '.' => Cell::Empty,
-                'S' => Cell::Start,
-                '^' => Cell::Splitter,
-                _ => panic!("Invalid character"),
-            },
-            false,
-        );
-
+    fn part2(&mut self, grid: &Grid<Cell>, _context: &RunContext) -> Result<Answer> {
         let mut splitter_cache: HashMap<Coord, u64> = HashMap::new();
 
         fn traverse_grid(
@@ -255,12 +295,22 @@ impl Solution for Day7 {
             }
         }
 
-        traverse_grid(
-            &grid,
-            &mut splitter_cache,
-            grid.find(Cell::Start).unwrap() + Direction::Down,
-        )
-        .to_string()
+        let start = grid
+            .find(Cell::Start)
+            .context("no start 'S' in the manifold")?;
+
+        Ok(traverse_grid(grid, &mut splitter_cache, start + Direction::Down).into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(0).part1(21).part2(40)]
+    }
+
+    fn implementations(&self) -> Vec<Implementation<Self>> {
+        vec![
+            Implementation::part1("sweep", |_, grid, _| Ok(sweep(grid)?.0.into())),
+            Implementation::part2("sweep", |_, grid, _| Ok(sweep(grid)?.1.into())),
+        ]
     }
 }
 
@@ -272,8 +322,10 @@ mod tests {
     fn test_part1() {
         let mut solution = Day7::new();
         assert_eq!(
-            solution.part1(
-                r#".......S.......
+            solution
+                .solve(
+                    1,
+                    r#".......S.......
 ...............
 .......^.......
 ...............
@@ -289,8 +341,9 @@ mod tests {
 ...............
 .^.^.^.^.^...^.
 ..............."#
-            ),
-            String::from("21")
+                )
+                .unwrap(),
+            Answer::from(21)
         );
     }
 
@@ -298,8 +351,10 @@ mod tests {
     fn test_part2() {
         let mut solution = Day7::new();
         assert_eq!(
-            solution.part2(
-                r#".......S.......
+            solution
+                .solve(
+                    2,
+                    r#".......S.......
 ...............
 .......^.......
 ...............
@@ -315,8 +370,9 @@ mod tests {
 ...............
 .^.^.^.^.^...^.
 ..............."#
-            ),
-            String::from("40")
+                )
+                .unwrap(),
+            Answer::from(40)
         );
     }
 }
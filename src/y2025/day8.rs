@@ -54,30 +54,32 @@
 
 use std::collections::HashMap;
 
+use anyhow::{Context, Result, bail, ensure};
 use itertools::Itertools;
+use rand::Rng;
 
-use crate::Solution;
+use crate::{Answer, Example, FromInput, RunContext, Solution, generate, parse_lines};
 
-pub struct Day8 {}
+type JunctionBox = (i64, i64, i64);
 
-impl Solution for Day8 {
-    fn new() -> Self {
-        Self {}
-    }
+fn parse_junction_box(line: &str) -> Result<JunctionBox> {
+    line.split(',')
+        .map(|part| part.parse::<i64>().context("invalid coordinate"))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .collect_tuple()
+        .context("expected three coordinates")
+}
 
-    fn part1(&mut self, input: &str) -> String {
-        let coords: Vec<(i64, i64, i64)> = input
-            .lines()
-            .map(|line| {
-                let parts: Vec<i64> = line
-                    .split(',')
-                    .map(|part| part.parse::<i64>().unwrap())
-                    .collect();
-                (parts[0], parts[1], parts[2])
-            })
-            .collect();
+/// The junction boxes, and every pair of them sorted from closest to furthest apart.
+pub struct Playground {
+    coords: Vec<JunctionBox>,
+    distances: Vec<(f64, JunctionBox, JunctionBox)>,
+}
 
-        let mut junctions: HashMap<(i64, i64, i64), usize> = HashMap::new();
+impl FromInput for Playground {
+    fn from_input(input: &str) -> Result<Self> {
+        let coords = parse_lines(input, parse_junction_box)?;
 
         let mut distances = coords
             .iter()
@@ -88,11 +90,32 @@ impl Solution for Day8 {
                 let dist = ((a.0 - b.0).pow(2) + (a.1 - b.1).pow(2) + (a.2 - b.2).pow(2)) as f64;
                 (dist.sqrt(), a, b)
             })
-            .collect::<Vec<(f64, (i64, i64, i64), (i64, i64, i64))>>();
+            .collect::<Vec<_>>();
 
         distances.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
 
-        for (_, a, b) in distances.iter().take(if cfg!(test) { 10 } else { 1000 }) {
+        Ok(Self { coords, distances })
+    }
+}
+
+pub struct Day8 {}
+
+register_solution!(Day8, year = 2025, day = 8, title = "Playground");
+
+impl Solution for Day8 {
+    type Parsed = Playground;
+
+    fn new() -> Self {
+        Self {}
+    }
+
+    fn part1(&mut self, input: &Playground, context: &RunContext) -> Result<Answer> {
+        let Playground { coords, distances } = input;
+        let connections = context.param("connections", 1000)?;
+
+        let mut junctions: HashMap<JunctionBox, usize> = HashMap::new();
+
+        for (_, a, b) in distances.iter().take(connections) {
             let group_a = junctions.get(a).cloned();
             let group_b = junctions.get(b).cloned();
 
@@ -100,7 +123,7 @@ impl Solution for Day8 {
                 (Some(ga), Some(gb)) if ga != gb => {
                     let min_group = ga.min(gb);
                     let max_group = ga.max(gb);
-                    for (_, group) in junctions.iter_mut() {
+                    for group in junctions.values_mut() {
                         if *group == max_group {
                             *group = min_group;
                         }
@@ -126,36 +149,24 @@ impl Solution for Day8 {
             *group_sizes.entry(*group).or_insert(0) += 1;
         }
         let mut sizes: Vec<usize> = group_sizes.values().cloned().collect();
+        // Boxes no connection reached are circuits of their own
+        sizes.resize(
+            sizes.len() + coords.len().saturating_sub(junctions.len()),
+            1,
+        );
         sizes.sort_by(|a, b| b.cmp(a));
-        (sizes[0] * sizes[1] * sizes[2]).to_string()
+        ensure!(
+            sizes.len() >= 3,
+            "expected at least 3 circuits, found {}",
+            sizes.len()
+        );
+        Ok((sizes[0] * sizes[1] * sizes[2]).into())
     }
 
-    fn part2(&mut self, input: &str) -> String {
-        let coords: Vec<(i64, i64, i64)> = input
-            .lines()
-            .map(|line| {
-                let parts: Vec<i64> = line
-                    .split(',')
-                    .map(|part| part.parse::<i64>().unwrap())
-                    .collect();
-                (parts[0], parts[1], parts[2])
-            })
-            .collect();
-
-        let mut junctions: HashMap<(i64, i64, i64), usize> = HashMap::new();
-
-        let mut distances = coords
-            .iter()
-            .combinations(2)
-            .map(|v| {
-                let a = *v[0];
-                let b = *v[1];
-                let dist = ((a.0 - b.0).pow(2) + (a.1 - b.1).pow(2) + (a.2 - b.2).pow(2)) as f64;
-                (dist.sqrt(), a, b)
-            })
-            .collect::<Vec<(f64, (i64, i64, i64), (i64, i64, i64))>>();
+    fn part2(&mut self, input: &Playground, _context: &RunContext) -> Result<Answer> {
+        let Playground { coords, distances } = input;
 
-        distances.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        let mut junctions: HashMap<JunctionBox, usize> = HashMap::new();
 
         for (_, a, b) in distances.iter() {
             let group_a = junctions.get(a).cloned();
@@ -165,14 +176,14 @@ impl Solution for Day8 {
                 (Some(ga), Some(gb)) if ga != gb => {
                     let min_group = ga.min(gb);
                     let max_group = ga.max(gb);
-                    for (_, group) in junctions.iter_mut() {
+                    for group in junctions.values_mut() {
                         if *group == max_group {
                             *group = min_group;
                         }
                     }
 
                     if min_group == 0 && junctions.len() == coords.len() {
-                        return (a.0 * b.0).to_string();
+                        return Ok((a.0 * b.0).into());
                     }
                 }
                 (Some(ga), None) => {
@@ -190,13 +201,42 @@ impl Solution for Day8 {
             }
 
             if junctions.len() == coords.len() && junctions.values().all(|&g| g == 0) {
-                return (a.0 * b.0).to_string();
+                return Ok((a.0 * b.0).into());
             }
         }
 
-        dbg!(junctions.len(), coords.len());
+        bail!(
+            "only connected {} of {} junction boxes into one circuit",
+            junctions.len(),
+            coords.len()
+        );
+    }
 
-        unreachable!();
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example::new(0)
+                .param("connections", 10)
+                .part1(40)
+                .part2(25272),
+        ]
+    }
+
+    /// `size` junction boxes anywhere in a cube as big as the real input's.
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        let mut rng = generate::rng(seed);
+        let boxes = (0..size)
+            .map(|_| {
+                let [x, y, z] = [(); 3].map(|_| rng.gen_range(0..100_000));
+                format!("{},{},{}", x, y, z)
+            })
+            .collect::<Vec<_>>();
+        Some(boxes.join("\n"))
+    }
+
+    /// Part 1's 1000 connections join everything on small inputs, so make one for every
+    /// two boxes like the example does.
+    fn generated_params(&self, size: usize) -> Vec<(String, String)> {
+        vec![("connections".to_string(), (size / 2).max(3).to_string())]
     }
 }
 
@@ -208,8 +248,10 @@ mod tests {
     fn test_part1() {
         let mut solution = Day8::new();
         assert_eq!(
-            solution.part1(
-                r#"162,817,812
+            solution
+                .solve_with(
+                    1,
+                    r#"162,817,812
 57,618,57
 906,360,560
 592,479,940
@@ -228,9 +270,11 @@ mod tests {
 941,993,340
 862,61,35
 984,92,344
-425,690,689"#
-            ),
-            String::from("40")
+425,690,689"#,
+                    &RunContext::example().with_param("connections", 10)
+                )
+                .unwrap(),
+            Answer::from(40)
         );
     }
 
@@ -238,8 +282,10 @@ mod tests {
     fn test_part2() {
         let mut solution = Day8::new();
         assert_eq!(
-            solution.part2(
-                r#"162,817,812
+            solution
+                .solve(
+                    2,
+                    r#"162,817,812
 57,618,57
 906,360,560
 592,479,940
@@ -259,8 +305,9 @@ mod tests {
 862,61,35
 984,92,344
 425,690,689"#
-            ),
-            String::from("25272")
+                )
+                .unwrap(),
+            Answer::from(25272)
         );
     }
 }
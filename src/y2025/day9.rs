@@ -85,28 +85,91 @@
 //! ```
 //! Using two red tiles as opposite corners, what is the largest area of any rectangle you can make?
 
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result, bail, ensure};
 use itertools::Itertools;
+use rand::Rng;
 
 use crate::{
-    Solution,
+    Answer, Example, RunContext, Solution,
     direction::Direction,
+    generate,
     grid::{Coord, Grid},
+    parse_lines,
 };
 
+fn parse_tile(line: &str) -> Result<(i64, i64)> {
+    let (x, y) = line.split_once(',').context("missing ','")?;
+    Ok((
+        x.parse().context("invalid x coordinate")?,
+        y.parse().context("invalid y coordinate")?,
+    ))
+}
+
+/// Checks that `tiles` go round a loop part 2 can draw: at least two different tiles,
+/// none of them repeated or off the floor, each in line with the next and the last in
+/// line with the first.
+fn check_loop(tiles: &[(i64, i64)]) -> Result<()> {
+    ensure!(tiles.len() >= 2, "need at least two red tiles");
+    ensure!(
+        tiles.iter().all_unique(),
+        "red tiles can't repeat, but some do"
+    );
+
+    if let Some((x, y)) = tiles.iter().find(|&&(x, y)| x < 0 || y < 0) {
+        bail!("red tile {},{} is off the floor", x, y);
+    }
+    for (i, &(x, y)) in tiles.iter().enumerate() {
+        let (next_x, next_y) = tiles[(i + 1) % tiles.len()];
+        ensure!(
+            x == next_x || y == next_y,
+            "red tiles {},{} and {},{} follow each other but aren't in a row or column",
+            x,
+            y,
+            next_x,
+            next_y
+        );
+    }
+
+    Ok(())
+}
+
+/// Maps each of `values` to a row or column of a much smaller grid, keeping their order
+/// and leaving one row or column for each gap between them, so the floor can be drawn
+/// without a tile for every position.
+///
+/// Every position in a gap looks the same, so one stands in for all of them. The
+/// gap before the first value stands in for the positions from 0.
+fn compress(values: impl IntoIterator<Item = i64>) -> BTreeMap<i64, i64> {
+    let mut compressed = BTreeMap::new();
+    let mut index = 0;
+    let mut previous = -1;
+
+    for value in values.into_iter().sorted().dedup() {
+        if value - previous > 1 {
+            index += 1;
+        }
+        compressed.insert(value, index);
+        index += 1;
+        previous = value;
+    }
+
+    compressed
+}
+
 pub struct Day9 {}
 
+register_solution!(Day9, year = 2025, day = 9, title = "Movie Theater");
+
 impl Solution for Day9 {
     fn new() -> Self {
         Self {}
     }
 
-    fn part1(&mut self, input: &str) -> String {
-        input
-            .lines()
-            .map(|line| {
-                let (x, y) = line.split_once(',').unwrap();
-                (x.parse::<i64>().unwrap(), y.parse::<i64>().unwrap())
-            })
+    fn part1(&mut self, input: &String, _context: &RunContext) -> Result<Answer> {
+        Ok(parse_lines(input, parse_tile)?
+            .into_iter()
             .combinations(2)
             .map(|pair| {
                 let (x1, y1) = pair[0];
@@ -116,25 +179,29 @@ impl Solution for Day9 {
                 width * height
             })
             .max()
-            .unwrap()
-            .to_string()
+            .context("need at least two red tiles")?
+            .into())
     }
 
-    fn known_solution_part1(&self) -> Option<String> {
-        Some(4750176210i64.to_string())
+    fn known_solution_part1(&self) -> Option<Answer> {
+        Some(4750176210i64.into())
     }
 
-    fn part2(&mut self, input: &str) -> String {
-        let coords = input
-            .lines()
-            .map(|line| {
-                let (x, y) = line.split_once(',').unwrap();
-                Coord::new(y.parse().unwrap(), x.parse().unwrap())
-            })
+    fn part2(&mut self, input: &String, _context: &RunContext) -> Result<Answer> {
+        let tiles = parse_lines(input, parse_tile)?;
+        check_loop(&tiles)?;
+
+        // The real floor is far too big to draw a tile at a time, but only the order of
+        // the red tiles matters until the areas are worked out
+        let xs = compress(tiles.iter().map(|&(x, _)| x));
+        let ys = compress(tiles.iter().map(|&(_, y)| y));
+        let coords = tiles
+            .iter()
+            .map(|(x, y)| Coord::new(ys[y], xs[x]))
             .collect_vec();
 
-        let max_row = coords.iter().max_by_key(|c| c.row()).unwrap().row() + 1;
-        let max_col = coords.iter().max_by_key(|c| c.col()).unwrap().col() + 1;
+        let max_row = ys.values().max().context("need at least one red tile")? + 1;
+        let max_col = xs.values().max().context("need at least one red tile")? + 1;
 
         let mut grid = Grid::new_blank(max_col, max_row, 0, false);
         grid.trace_coord_list(&coords, |&v, _, _| v + 1, true);
@@ -221,12 +288,14 @@ impl Solution for Day9 {
             .map(|c| (c, SearchResults::do_search(&grid, &transposed_grid, *c)))
             .collect_vec();
 
-        searched
+        // Areas come from the real positions, everything else from the compressed ones
+        Ok(searched
             .into_iter()
+            .zip(tiles.iter().map(|&(x, y)| Coord::new(y, x)))
             .combinations(2)
-            .map(|pair| (pair[0], pair[1], pair[0].0.area(*pair[1].0)))
+            .map(|pair| (pair[0].0, pair[1].0, pair[0].1.area(pair[1].1)))
             .sorted_by(|(_, _, area1), (_, _, area2)| area2.cmp(area1))
-            .filter(|((c1, res1), (c2, res2), _)| {
+            .find(|((c1, res1), (c2, res2), _)| {
                 let direction = (**c2 - **c1).direction();
                 // Rows are intuitively backwards since we count them from the top
                 match direction {
@@ -258,14 +327,66 @@ impl Solution for Day9 {
                     _ => false,
                 }
             })
-            .next()
             .map(|(_, _, area)| area)
-            .unwrap()
-            .to_string()
+            .context("no rectangle fits inside the red and green tiles")?
+            .into())
+    }
+
+    fn known_solution_part2(&self) -> Option<Answer> {
+        Some(1574684850.into())
     }
 
-    fn known_solution_part2(&self) -> Option<String> {
-        Some(1574684850.to_string())
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(0).part1(50).part2(24)]
+    }
+
+    /// A rectilinear polygon of `size` columns side by side, each spanning a random
+    /// range of rows that overlaps its neighbours', listed corner by corner.
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        let mut rng = generate::rng(seed);
+        let columns = size.max(1);
+        let half = columns as i64 / 2 + 2;
+
+        let mut xs = vec![rng.gen_range(0..10)];
+        for _ in 0..columns {
+            xs.push(xs.last().unwrap() + rng.gen_range(2..10));
+        }
+
+        // Every bottom is below every top, so neighbouring columns always overlap. Corners
+        // must turn, so neighbouring tops and bottoms differ
+        let mut spans: Vec<(i64, i64)> = vec![];
+        for _ in 0..columns {
+            let span = loop {
+                let span = (rng.gen_range(0..half), rng.gen_range(half..2 * half));
+                if spans
+                    .last()
+                    .is_none_or(|last| last.0 != span.0 && last.1 != span.1)
+                {
+                    break span;
+                }
+            };
+            spans.push(span);
+        }
+
+        // Up the left side, along the tops, down the right side and back along the bottoms
+        let mut corners = vec![(xs[0], spans[0].0), (xs[0], spans[0].1)];
+        for i in 1..columns {
+            corners.push((xs[i], spans[i - 1].1));
+            corners.push((xs[i], spans[i].1));
+        }
+        corners.push((xs[columns], spans[columns - 1].1));
+        corners.push((xs[columns], spans[columns - 1].0));
+        for i in (1..columns).rev() {
+            corners.push((xs[i], spans[i].0));
+            corners.push((xs[i], spans[i - 1].0));
+        }
+
+        Some(
+            corners
+                .iter()
+                .map(|(x, y)| format!("{},{}", x, y))
+                .join("\n"),
+        )
     }
 }
 
@@ -273,12 +394,41 @@ impl Solution for Day9 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_compress() {
+        assert_eq!(
+            compress([7, 0, 1, 3, 7, 100]),
+            BTreeMap::from([(0, 0), (1, 1), (3, 3), (7, 5), (100, 7)])
+        );
+        assert_eq!(compress([5]), BTreeMap::from([(5, 1)]));
+    }
+
+    #[test]
+    fn test_check_loop() {
+        let mut solution = Day9::new();
+        for input in [
+            "4,4",
+            "5,5\n5,5",
+            "1,-1\n1,4",
+            "1,1\n-3,4",
+            "1,1\n1,4\n3,4\n3,2",
+        ] {
+            assert!(solution.solve(2, input).is_err(), "{:?}", input);
+        }
+        assert_eq!(
+            solution.solve(2, "1,1\n1,4\n3,4\n3,1").unwrap(),
+            Answer::from(12)
+        );
+    }
+
     #[test]
     fn test_part1() {
         let mut solution = Day9::new();
         assert_eq!(
-            solution.part1(
-                r#"7,1
+            solution
+                .solve(
+                    1,
+                    r#"7,1
 11,1
 11,7
 9,7
@@ -286,8 +436,9 @@ mod tests {
 2,5
 2,3
 7,3"#
-            ),
-            String::from("50")
+                )
+                .unwrap(),
+            Answer::from(50)
         );
     }
 
@@ -295,8 +446,10 @@ mod tests {
     fn test_part2() {
         let mut solution = Day9::new();
         assert_eq!(
-            solution.part2(
-                r#"7,1
+            solution
+                .solve(
+                    2,
+                    r#"7,1
 11,1
 11,7
 9,7
@@ -304,8 +457,9 @@ mod tests {
 2,5
 2,3
 7,3"#
-            ),
-            String::from("24")
+                )
+                .unwrap(),
+            Answer::from(24)
         );
     }
 }